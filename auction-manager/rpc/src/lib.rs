@@ -0,0 +1,197 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+//
+// This file is part of Ethical DeFi.
+//
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: BUSL-1.1 (Business Source License 1.1)
+
+//! RPC interface for the auction manager module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use primitives::{AuctionId, Balance, CurrencyId};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use auction_manager_runtime_api::{
+	AuctionManagerApi as AuctionManagerRuntimeApi, CollateralAuctionDetails, CollateralAuctionStats,
+	SettledCollateralAuction,
+};
+
+#[rpc]
+pub trait AuctionManagerApi<BlockHash, AccountId, BlockNumber> {
+	/// Get the live state of all active collateral auctions.
+	#[rpc(name = "auctionManager_getActiveCollateralAuctions")]
+	fn get_active_collateral_auctions(
+		&self,
+		at: Option<BlockHash>,
+	) -> Result<Vec<CollateralAuctionDetails<AccountId, BlockNumber>>>;
+
+	/// Get the live state of collateral auction `auction_id`.
+	#[rpc(name = "auctionManager_getCollateralAuctionDetails")]
+	fn get_collateral_auction_details(
+		&self,
+		auction_id: AuctionId,
+		at: Option<BlockHash>,
+	) -> Result<Option<CollateralAuctionDetails<AccountId, BlockNumber>>>;
+
+	/// Get the payment amount and the collateral amount for sale if
+	/// collateral auction `auction_id` is bid at `bid_price`.
+	#[rpc(name = "auctionManager_getCollateralAuctionAmountsAtBid")]
+	fn get_collateral_auction_amounts_at_bid(
+		&self,
+		auction_id: AuctionId,
+		bid_price: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Option<(Balance, Balance)>>;
+
+	/// Get the archived records of finalized collateral auctions, from the
+	/// oldest to the latest.
+	#[rpc(name = "auctionManager_getSettledCollateralAuctions")]
+	fn get_settled_collateral_auctions(
+		&self,
+		at: Option<BlockHash>,
+	) -> Result<Vec<SettledCollateralAuction<AccountId, BlockNumber>>>;
+
+	/// Get the archived record of finalized collateral auction `auction_id`.
+	#[rpc(name = "auctionManager_getSettledCollateralAuction")]
+	fn get_settled_collateral_auction(
+		&self,
+		auction_id: AuctionId,
+		at: Option<BlockHash>,
+	) -> Result<Option<SettledCollateralAuction<AccountId, BlockNumber>>>;
+
+	/// Get the aggregate statistics of the finalized auctions of
+	/// `collateral_type`.
+	#[rpc(name = "auctionManager_getCollateralAuctionStats")]
+	fn get_collateral_auction_stats(
+		&self,
+		collateral_type: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<CollateralAuctionStats>;
+}
+
+/// A struct that implements the [`AuctionManagerApi`].
+pub struct AuctionManager<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> AuctionManager<C, B> {
+	/// Create new `AuctionManager` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber> AuctionManagerApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for AuctionManager<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AuctionManagerRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+{
+	fn get_active_collateral_auctions(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<CollateralAuctionDetails<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.active_collateral_auctions(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get active collateral auctions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_collateral_auction_details(
+		&self,
+		auction_id: AuctionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<CollateralAuctionDetails<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.collateral_auction_details(&at, auction_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get collateral auction details.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_collateral_auction_amounts_at_bid(
+		&self,
+		auction_id: AuctionId,
+		bid_price: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(Balance, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.collateral_auction_amounts_at_bid(&at, auction_id, bid_price)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get collateral auction amounts at bid.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_settled_collateral_auctions(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<SettledCollateralAuction<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.settled_collateral_auctions(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get settled collateral auctions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_settled_collateral_auction(
+		&self,
+		auction_id: AuctionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SettledCollateralAuction<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.settled_collateral_auction(&at, auction_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get settled collateral auction.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_collateral_auction_stats(
+		&self,
+		collateral_type: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CollateralAuctionStats> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.collateral_auction_stats(&at, collateral_type).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get collateral auction stats.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
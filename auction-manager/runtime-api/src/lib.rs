@@ -0,0 +1,49 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+//
+// This file is part of Ethical DeFi.
+//
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: BUSL-1.1 (Business Source License 1.1)
+
+//! Runtime API definition for the auction manager module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use primitives::{AuctionId, Balance, CurrencyId};
+use sp_std::prelude::*;
+
+pub use auction_manager::{CollateralAuctionDetails, CollateralAuctionStats, SettledCollateralAuction};
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionManagerApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Get the live state of all active collateral auctions.
+		fn active_collateral_auctions() -> Vec<CollateralAuctionDetails<AccountId, BlockNumber>>;
+
+		/// Get the live state of collateral auction `auction_id`.
+		fn collateral_auction_details(auction_id: AuctionId) -> Option<CollateralAuctionDetails<AccountId, BlockNumber>>;
+
+		/// Get the payment amount and the collateral amount for sale if
+		/// collateral auction `auction_id` is bid at `bid_price`.
+		fn collateral_auction_amounts_at_bid(auction_id: AuctionId, bid_price: Balance) -> Option<(Balance, Balance)>;
+
+		/// Get the archived records of finalized collateral auctions, from
+		/// the oldest to the latest.
+		fn settled_collateral_auctions() -> Vec<SettledCollateralAuction<AccountId, BlockNumber>>;
+
+		/// Get the archived record of finalized collateral auction
+		/// `auction_id`.
+		fn settled_collateral_auction(auction_id: AuctionId) -> Option<SettledCollateralAuction<AccountId, BlockNumber>>;
+
+		/// Get the aggregate statistics of the finalized auctions of
+		/// `collateral_type`.
+		fn collateral_auction_stats(collateral_type: CurrencyId) -> CollateralAuctionStats;
+	}
+}
@@ -13,34 +13,43 @@
 //! business. Auction types include:
 //!   - `collateral auction`: sell collateral assets for getting stable currency to eliminate the
 //!     system's bad debit by auction
+//!   - `debit auction`: sell newly issued standard currency for getting stable currency to
+//!     recapitalise the system's bad debit by reverse auction
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 #![allow(clippy::unnecessary_unwrap)]
 
-use frame_support::{log, pallet_prelude::*, transactional};
+use frame_support::{log, pallet_prelude::*, traits::StorageVersion, transactional, PalletId};
 use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
 };
-use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, OnNewBidResult};
+use orml_traits::{Auction, AuctionHandler, AuctionInfo, Change, MultiCurrency, OnNewBidResult};
 use orml_utilities::OffchainErr;
 use primitives::{AuctionId, Balance, CurrencyId};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	offchain::{
 		storage::StorageValueRef,
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{CheckedDiv, Saturating, Zero},
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
 	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate, SwapLimit};
+use cdp_treasury::AuctionProceedsManager;
+use loans::AuctionBidFunding;
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, Price, PriceProvider, Rate, Ratio,
+	SerpTreasury, SwapLimit,
+};
 
 mod mock;
 mod tests;
@@ -75,6 +84,11 @@ pub struct CollateralAuctionItem<AccountId, BlockNumber> {
 	/// bidder
 	#[codec(compact)]
 	target: Balance,
+	/// The debt plus penalty owed by the refund recipient to be covered by
+	/// the proceeds, it is kept when the auction restarts with a discounted
+	/// target, and zero if nothing is owed
+	#[codec(compact)]
+	debt_owed: Balance,
 	/// Auction start time
 	start_time: BlockNumber,
 }
@@ -113,10 +127,236 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 	}
 }
 
+/// Collateral auction info stored before `debt_owed` was added, kept to
+/// migrate the existing collateral auctions
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub(crate) struct OldCollateralAuctionItem<AccountId, BlockNumber> {
+	pub(crate) refund_recipient: AccountId,
+	pub(crate) currency_id: CurrencyId,
+	#[codec(compact)]
+	pub(crate) initial_amount: Balance,
+	#[codec(compact)]
+	pub(crate) amount: Balance,
+	#[codec(compact)]
+	pub(crate) target: Balance,
+	pub(crate) start_time: BlockNumber,
+}
+
+/// Information of a debit auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct DebitAuctionItem<BlockNumber> {
+	/// Currency newly issued to the winner
+	currency_id: CurrencyId,
+	/// Initial amount of issuance currency for sale
+	#[codec(compact)]
+	initial_amount: Balance,
+	/// Current amount of issuance currency for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Fix amount of stable currency to be paid by the winner
+	#[codec(compact)]
+	fix: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+}
+
+impl<BlockNumber> DebitAuctionItem<BlockNumber> {
+	/// Return the amount for sale at specific last bid price and new bid
+	/// price
+	fn amount_for_sale(&self, last_bid_price: Balance, new_bid_price: Balance) -> Balance {
+		if new_bid_price > last_bid_price && new_bid_price > self.fix {
+			Rate::checked_from_rational(sp_std::cmp::max(last_bid_price, self.fix), new_bid_price)
+				.and_then(|n| n.checked_mul_int(self.amount))
+				.unwrap_or(self.amount)
+		} else {
+			self.amount
+		}
+	}
+}
+
+/// Auction parameters of a collateral type, the ones which are `None` fall
+/// back to the pallet constants.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct CollateralAuctionParams<BlockNumber> {
+	/// The minimum increment size of each bid compared to the previous one.
+	pub minimum_increment_size: Option<Rate>,
+	/// The extended time for the auction to end after each successful bid.
+	pub auction_time_to_close: Option<BlockNumber>,
+	/// The soft cap of the total duration of the auction.
+	pub auction_duration_soft_cap: Option<BlockNumber>,
+	/// The hard cap of the total duration of the auction, bids can no longer
+	/// extend the auction beyond it.
+	pub auction_duration_hard_cap: Option<BlockNumber>,
+	/// The policy to restart the auction which ended without reaching the
+	/// target, `None` means abort it.
+	pub restart_policy: Option<CollateralAuctionRestartPolicy>,
+	/// The policy to distribute the proceeds of the auction taken by DEX,
+	/// `None` means `ReturnExcessProceeds`.
+	pub proceeds_distribution: Option<ProceedsDistribution>,
+	/// The maximum deviation of the DEX price taking the auction below the
+	/// oracle price, `None` means no check.
+	pub dex_take_max_deviation: Option<Ratio>,
+	/// The period of the exponential moving average DEX price of selling the
+	/// collateral in auction, the DEX price taking the auction must not be
+	/// below it more than `dex_take_max_deviation`, or at all if that is
+	/// `None`. `None` means no check.
+	pub dex_average_price_period: Option<BlockNumber>,
+}
+
+/// Distribution policy of the proceeds of collateral auctions taken by DEX.
+/// The proceeds above the debt plus penalty owed are returned to the refund
+/// recipient either way.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProceedsDistribution {
+	/// Sell only the collateral to cover the target, and refund the rest
+	/// collateral to the refund recipient.
+	RefundCollateral,
+	/// Sell all the collateral, and return the stable proceeds above the
+	/// target to the refund recipient.
+	ReturnExcessProceeds,
+}
+
+/// Restart policy of the collateral auctions which ended without reaching
+/// the target.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct CollateralAuctionRestartPolicy {
+	/// The ratio of the target to be discounted when re-list the collateral.
+	pub target_discount: Rate,
+	/// The maximum times to re-list the collateral, abort the auction when
+	/// reached.
+	pub max_restarts: u32,
+}
+
+/// Live state of an active collateral auction for bidders.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CollateralAuctionDetails<AccountId, BlockNumber> {
+	/// The auction id.
+	pub auction_id: AuctionId,
+	/// Collateral type for sale.
+	pub collateral_type: CurrencyId,
+	/// Refund recipient for may receive refund.
+	pub refund_recipient: AccountId,
+	/// Current collateral amount for sale.
+	pub amount: Balance,
+	/// Target sales amount of the auction, zero if it is always forward.
+	pub target: Balance,
+	/// Auction start time.
+	pub start_time: BlockNumber,
+	/// Auction end time.
+	pub end_time: Option<BlockNumber>,
+	/// The current bidder and bid price.
+	pub last_bid: Option<(AccountId, Balance)>,
+	/// Whether the auction is in reverse stage at the current bid price.
+	pub in_reverse_stage: bool,
+	/// The minimum bid price to be accepted by the next bid.
+	pub minimum_next_bid: Balance,
+	/// The discount of the collateral price implied by the minimum next bid
+	/// compared to the oracle price, `None` if the oracle price is
+	/// unavailable.
+	pub oracle_discount: Option<Rate>,
+}
+
+/// Outcome of a finalized collateral auction.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CollateralAuctionOutcome {
+	/// Dealt by the last bidder.
+	Dealt,
+	/// Taken by DEX.
+	DEXTake,
+	/// Re-listed with a lower target as the new auction.
+	Restarted(AuctionId),
+	/// Aborted, the collateral is reprocessed by cdp treasury.
+	Aborted,
+	/// Cancelled after emergency shutdown.
+	Cancelled,
+}
+
+/// Archived record of a finalized collateral auction.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SettledCollateralAuction<AccountId, BlockNumber> {
+	/// The auction id.
+	pub auction_id: AuctionId,
+	/// Collateral type for sale.
+	pub collateral_type: CurrencyId,
+	/// How the auction is finalized.
+	pub outcome: CollateralAuctionOutcome,
+	/// Refund recipient of the auction.
+	pub refund_recipient: AccountId,
+	/// Collateral amount in the auction when finalized.
+	pub collateral_amount: Balance,
+	/// Target sales amount of the auction, zero if it is always forward.
+	pub target: Balance,
+	/// Collateral amount sold.
+	pub sold_amount: Balance,
+	/// Stable currency amount received for the collateral sold.
+	pub proceeds: Balance,
+	/// The winner of the auction dealt.
+	pub winner: Option<AccountId>,
+	/// The collateral price the auction settled at in stable currency, `None`
+	/// if nothing sold.
+	pub settlement_price: Option<Price>,
+	/// The oracle price of the collateral in stable currency when finalized.
+	pub oracle_price: Option<Price>,
+	/// Auction start time.
+	pub start_time: BlockNumber,
+	/// Auction finalized time.
+	pub end_time: BlockNumber,
+}
+
+/// Aggregate statistics of the finalized collateral auctions of a collateral
+/// type.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct CollateralAuctionStats {
+	/// Number of auctions dealt by the last bidder.
+	pub dealt_count: u32,
+	/// Number of auctions taken by DEX.
+	pub dex_take_count: u32,
+	/// Number of auctions re-listed.
+	pub restarted_count: u32,
+	/// Number of auctions aborted.
+	pub aborted_count: u32,
+	/// Number of auctions cancelled.
+	pub cancelled_count: u32,
+	/// Total collateral amount sold.
+	pub sold_amount: Balance,
+	/// Total stable currency amount received for the collateral sold.
+	pub proceeds: Balance,
+	/// Total value of the collateral sold at the oracle price, only the
+	/// sales with oracle price are counted.
+	pub oracle_value: Balance,
+	/// Total proceeds of the sales with oracle price.
+	pub priced_proceeds: Balance,
+	/// Total target of the auctions dealt, taken by DEX or aborted.
+	pub target: Balance,
+	/// Total proceeds covering the target of these auctions.
+	pub recovered: Balance,
+	/// The average discount of the settlement price to the oracle price,
+	/// weighted by value. Sales above the oracle price lower it.
+	pub average_oracle_discount: Option<Rate>,
+	/// The ratio of the target covered by the proceeds.
+	pub recovery_rate: Option<Ratio>,
+}
+
+impl CollateralAuctionStats {
+	fn update_rates(&mut self) {
+		self.average_oracle_discount = Rate::checked_from_rational(self.priced_proceeds, self.oracle_value)
+			.map(|ratio| Rate::one().saturating_sub(ratio));
+		self.recovery_rate = Ratio::checked_from_rational(self.recovered, self.target);
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -134,6 +374,11 @@ pub mod module {
 		#[pallet::constant]
 		type AuctionDurationSoftCap: Get<Self::BlockNumber>;
 
+		/// The hard cap of the total duration of the auction, bids can no
+		/// longer extend the auction beyond it
+		#[pallet::constant]
+		type AuctionDurationHardCap: Get<Self::BlockNumber>;
+
 		/// The stable currency id
 		#[pallet::constant]
 		type GetSetUSDId: Get<CurrencyId>;
@@ -145,7 +390,8 @@ pub mod module {
 		type Auction: Auction<Self::AccountId, Self::BlockNumber, AuctionId = AuctionId, Balance = Balance>;
 
 		/// CDP treasury to escrow assets related to auction
-		type CDPTreasury: CDPTreasuryExtended<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+		type CDPTreasury: CDPTreasuryExtended<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>
+			+ AuctionProceedsManager<Self::AccountId>;
 
 		/// DEX to get exchange info
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
@@ -153,6 +399,10 @@ pub mod module {
 		/// The price source of currencies
 		type PriceSource: PriceProvider<CurrencyId>;
 
+		/// SERP Treasury to issue the standard currency sold by debit
+		/// auctions
+		type SerpTreasury: SerpTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
 		/// A configuration for base priority of unsigned transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
@@ -163,6 +413,26 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The origin which may update the auction parameters.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The auction manager's module id, keep the stable currency of active
+		/// bids in escrow until the auctions end.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of bids in a batch
+		#[pallet::constant]
+		type MaxBatchBids: Get<u32>;
+
+		/// The maximum number of finalized collateral auctions kept in the
+		/// archive, the oldest ones are pruned.
+		#[pallet::constant]
+		type MaxArchivedCollateralAuctions: Get<u32>;
+
+		/// The positions which fund collateral auction bids by minting debit.
+		type BidFunding: AuctionBidFunding<Self::AccountId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -173,6 +443,8 @@ pub mod module {
 		AuctionNotExists,
 		/// The collateral auction is in reverse stage now
 		InReverseStage,
+		/// The collateral auction is not in reverse stage
+		NotInReverseStage,
 		/// Feed price is invalid
 		InvalidFeedPrice,
 		/// Must after system shutdown
@@ -181,6 +453,16 @@ pub mod module {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// Must before system shutdown
+		MustBeforeShutdown,
+		/// The auction parameters are invalid
+		InvalidAuctionParams,
+		/// The auction is not started or already ended
+		AuctionNotActive,
+		/// The DEX price deviates from the oracle or average price too much
+		DEXPriceDeviated,
+		/// Too many bids in a batch
+		TooManyBids,
 	}
 
 	#[pallet::event]
@@ -195,6 +477,15 @@ pub mod module {
 		},
 		/// Active auction cancelled.
 		CancelAuction { auction_id: AuctionId },
+		/// Collateral auction in reverse stage settled to the last bidder at
+		/// the locked price after system shutdown.
+		ReverseAuctionSettled {
+			auction_id: AuctionId,
+			winner: T::AccountId,
+			collateral_amount: Balance,
+			payment_amount: Balance,
+			settle_price: Price,
+		},
 		/// Collateral auction dealt.
 		CollateralAuctionDealt {
 			auction_id: AuctionId,
@@ -211,6 +502,11 @@ pub mod module {
 			supply_collateral_amount: Balance,
 			target_stable_amount: Balance,
 		},
+		/// DEX taking collateral auction rejected as the price deviated.
+		DEXTakeRejected {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+		},
 		/// Collateral auction aborted.
 		CollateralAuctionAborted {
 			auction_id: AuctionId,
@@ -219,6 +515,73 @@ pub mod module {
 			target_stable_amount: Balance,
 			refund_recipient: T::AccountId,
 		},
+		/// Collateral auction ended without reaching the target and re-listed
+		/// with a lower target.
+		CollateralAuctionRestarted {
+			auction_id: AuctionId,
+			new_auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			target_stable_amount: Balance,
+			new_target_stable_amount: Balance,
+		},
+		/// Collateral auction bid placed through this pallet rather than the
+		/// `bid` of auction, in place of the `Bid` event of auction.
+		CollateralAuctionBid {
+			auction_id: AuctionId,
+			bidder: T::AccountId,
+			bid_price: Balance,
+		},
+		/// Collateral auction bid funded by minting debit against the position
+		/// of the bidder.
+		LoanFundedBid {
+			auction_id: AuctionId,
+			bidder: T::AccountId,
+			bid_price: Balance,
+			debit_amount: Balance,
+		},
+		/// Batch bids placed, the auctions failed are skipped.
+		BatchBid {
+			bidder: T::AccountId,
+			placed: Vec<(AuctionId, Balance)>,
+			failed: Vec<AuctionId>,
+		},
+		/// The proceeds of collateral auction distributed.
+		CollateralAuctionProceedsDistributed {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			proceeds: Balance,
+			covered_amount: Balance,
+			excess_amount: Balance,
+			refund_recipient: T::AccountId,
+		},
+		/// Debit auction created.
+		NewDebitAuction {
+			auction_id: AuctionId,
+			issuance_currency_id: CurrencyId,
+			initial_amount: Balance,
+			fix_debit_amount: Balance,
+		},
+		/// Debit auction dealt.
+		DebitAuctionDealt {
+			auction_id: AuctionId,
+			issuance_currency_id: CurrencyId,
+			issuance_amount: Balance,
+			winner: T::AccountId,
+			payment_amount: Balance,
+		},
+		/// Debit auction aborted without any bid.
+		DebitAuctionAborted {
+			auction_id: AuctionId,
+			issuance_currency_id: CurrencyId,
+			initial_amount: Balance,
+			fix_debit_amount: Balance,
+		},
+		/// The auction parameters of specific collateral type updated.
+		CollateralAuctionParamsUpdated {
+			collateral_type: CurrencyId,
+			new_params: CollateralAuctionParams<T::BlockNumber>,
+		},
 	}
 
 	/// Mapping from auction id to collateral auction info
@@ -244,11 +607,143 @@ pub mod module {
 	#[pallet::getter(fn total_target_in_auction)]
 	pub type TotalTargetInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// Mapping from auction id to debit auction info
+	///
+	/// DebitAuctions: map AuctionId => Option<DebitAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_auctions)]
+	pub type DebitAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, DebitAuctionItem<T::BlockNumber>, OptionQuery>;
+
+	/// Record of the total fix amount of stable currency of all active debit
+	/// auctions
+	///
+	/// TotalDebitInAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_debit_in_auction)]
+	pub type TotalDebitInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// Record of the total initial issuance amount of all active debit
+	/// auctions under specific issuance currency
+	///
+	/// TotalIssuanceInDebitAuction: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_issuance_in_debit_auction)]
+	pub type TotalIssuanceInDebitAuction<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Mapping from auction id to the stable currency amount paid by its
+	/// active bidder and held in escrow
+	///
+	/// BidEscrows: map AuctionId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn bid_escrow)]
+	pub type BidEscrows<T: Config> = StorageMap<_, Twox64Concat, AuctionId, Balance, ValueQuery>;
+
+	/// Mapping from collateral type to its auction parameters
+	///
+	/// CollateralAuctionParameters: map CurrencyId => CollateralAuctionParams
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_auction_params)]
+	pub type CollateralAuctionParameters<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CollateralAuctionParams<T::BlockNumber>, ValueQuery>;
+
+	/// Mapping from collateral auction id to the times its collateral has
+	/// been re-listed
+	///
+	/// CollateralAuctionRestarts: map AuctionId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_auction_restarts)]
+	pub type CollateralAuctionRestarts<T: Config> = StorageMap<_, Twox64Concat, AuctionId, u32, ValueQuery>;
+
+	/// Mapping from collateral auction id to the active bid funded by minting
+	/// debit, as the bidder, collateral type, the debit amount minted, its
+	/// value held in escrow and the mint fee held in escrow
+	///
+	/// LoanFundedBids: map AuctionId => Option<(AccountId, CurrencyId, Balance, Balance, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn loan_funded_bid)]
+	pub type LoanFundedBids<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, (T::AccountId, CurrencyId, Balance, Balance, Balance), OptionQuery>;
+
+	/// Mapping from collateral type to its exponential moving average DEX
+	/// price and the block it updated at
+	///
+	/// DEXAveragePrices: map CurrencyId => Option<(Price, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn dex_average_price)]
+	pub type DEXAveragePrices<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (Price, T::BlockNumber), OptionQuery>;
+
+	/// Mapping from auction id to the archived record of finalized collateral
+	/// auction
+	///
+	/// SettledCollateralAuctions: map AuctionId => Option<SettledCollateralAuction>
+	#[pallet::storage]
+	#[pallet::getter(fn settled_collateral_auction)]
+	pub type SettledCollateralAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, SettledCollateralAuction<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// Ring of the auction ids in the archive, indexed by archive slot
+	///
+	/// ArchivedCollateralAuctionIds: map u32 => Option<AuctionId>
+	#[pallet::storage]
+	pub type ArchivedCollateralAuctionIds<T: Config> = StorageMap<_, Twox64Concat, u32, AuctionId, OptionQuery>;
+
+	/// Record of the total number of collateral auctions archived
+	///
+	/// ArchivedCollateralAuctionCount: u64
+	#[pallet::storage]
+	#[pallet::getter(fn archived_collateral_auction_count)]
+	pub type ArchivedCollateralAuctionCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Mapping from collateral type to the aggregate statistics of its
+	/// finalized auctions
+	///
+	/// CollateralAuctionStatistics: map CurrencyId => CollateralAuctionStats
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_auction_stats)]
+	pub type CollateralAuctionStatistics<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CollateralAuctionStats, ValueQuery>;
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Update the exponential moving average DEX prices before any
+		/// transaction of the block can move them.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let count = Self::update_dex_average_prices(now);
+			T::WeightInfo::on_initialize(count)
+		}
+
+		/// Migrate the collateral auctions created before `debt_owed` was
+		/// stored, they owe their target.
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: u64 = 0;
+			CollateralAuctions::<T>::translate::<OldCollateralAuctionItem<T::AccountId, T::BlockNumber>, _>(
+				|_, old| {
+					count += 1;
+					Some(CollateralAuctionItem {
+						refund_recipient: old.refund_recipient,
+						currency_id: old.currency_id,
+						initial_amount: old.initial_amount,
+						amount: old.amount,
+						target: old.target,
+						debt_owed: old.target,
+						start_time: old.start_time,
+					})
+				},
+			);
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(count + 1, count + 1)
+		}
+
 		/// Start offchain worker in order to submit unsigned tx to cancel
 		/// active auction after system shutdown.
 		fn offchain_worker(now: T::BlockNumber) {
@@ -284,6 +779,117 @@ pub mod module {
 			Self::deposit_event(Event::CancelAuction { auction_id: id });
 			Ok(())
 		}
+
+		/// Settle collateral auction in reverse stage after system shutdown,
+		/// deal it to the last bidder at the locked price immediately.
+		///
+		/// The dispatch origin of this call must be _None_.
+		#[pallet::weight(T::WeightInfo::settle_reverse_auction())]
+		#[transactional]
+		pub fn settle_reverse_auction(origin: OriginFor<T>, id: AuctionId) -> DispatchResult {
+			ensure_none(origin)?;
+			ensure!(T::EmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
+			Self::do_settle_reverse_auction(id)
+		}
+
+		/// Bid for collateral auction, funding the bid by minting debit against
+		/// the position of the bidder under the collateral type of the auction.
+		/// The debit value is held in escrow directly and must not exceed the
+		/// payment of the bid, the rest of the payment is paid by the bidder. The
+		/// position must back the debit without the collateral to be won. If the
+		/// bid wins, the collateral is deposited into the position, otherwise the
+		/// debit is repaid from escrow.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: collateral auction id.
+		/// - `bid_price`: the bid price.
+		/// - `debit_amount`: the debit amount to mint for paying the bid.
+		#[pallet::weight(T::WeightInfo::bid_with_loan())]
+		#[transactional]
+		pub fn bid_with_loan(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			bid_price: Balance,
+			debit_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+			Self::do_bid_with_loan(&who, id, bid_price, debit_amount)
+		}
+
+		/// Bid for multiple collateral auctions at once. Each auction is bid
+		/// at the minimum acceptable price if it does not exceed the max price
+		/// given, the auctions failed are skipped.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `bids`: the collateral auction ids and the max prices.
+		#[pallet::weight(T::WeightInfo::batch_bid(bids.len() as u32))]
+		#[transactional]
+		pub fn batch_bid(origin: OriginFor<T>, bids: Vec<(AuctionId, Balance)>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+			ensure!(
+				bids.len() as u32 <= T::MaxBatchBids::get(),
+				Error::<T>::TooManyBids
+			);
+
+			let (placed, failed) = Self::do_batch_bid(&who, bids);
+			Self::deposit_event(Event::BatchBid {
+				bidder: who,
+				placed,
+				failed,
+			});
+			Ok(())
+		}
+
+		/// Update the auction parameters of specific collateral type, the
+		/// parameters which are `None` fall back to the pallet constants. The
+		/// new parameters only apply to the later bids and auctions.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `params`: the auction parameters.
+		#[pallet::weight((T::WeightInfo::set_collateral_auction_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_collateral_auction_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: CollateralAuctionParams<T::BlockNumber>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				params.minimum_increment_size.map_or(true, |size| size <= Rate::one())
+					&& params.auction_time_to_close.map_or(true, |time| !time.is_zero())
+					&& params.auction_duration_soft_cap.map_or(true, |cap| !cap.is_zero())
+					&& params.auction_duration_hard_cap.map_or(true, |cap| !cap.is_zero())
+					&& params
+						.auction_duration_soft_cap
+						.unwrap_or_else(T::AuctionDurationSoftCap::get)
+						<= params
+							.auction_duration_hard_cap
+							.unwrap_or_else(T::AuctionDurationHardCap::get)
+					&& params
+						.restart_policy
+						.map_or(true, |policy| policy.target_discount <= Rate::one())
+					&& params.dex_take_max_deviation.map_or(true, |deviation| deviation <= Ratio::one())
+					&& params.dex_average_price_period.map_or(true, |period| !period.is_zero()),
+				Error::<T>::InvalidAuctionParams
+			);
+
+			if params.dex_average_price_period.is_none() {
+				DEXAveragePrices::<T>::remove(currency_id);
+			}
+
+			CollateralAuctionParameters::<T>::insert(currency_id, params.clone());
+			Self::deposit_event(Event::CollateralAuctionParamsUpdated {
+				collateral_type: currency_id,
+				new_params: params,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -302,7 +908,27 @@ pub mod module {
 							return InvalidTransaction::Stale.into();
 						}
 					}
-				} else {
+				} else if !<DebitAuctions<T>>::contains_key(auction_id) {
+					return InvalidTransaction::Stale.into();
+				}
+
+				ValidTransaction::with_tag_prefix("AuctionManagerOffchainWorker")
+					.priority(T::UnsignedPriority::get())
+					.and_provides(auction_id)
+					.longevity(64_u64)
+					.propagate(true)
+					.build()
+			} else if let Call::settle_reverse_auction(auction_id) = call {
+				if !T::EmergencyShutdown::is_shutdown() {
+					return InvalidTransaction::Call.into();
+				}
+
+				// only collateral auction in reverse stage can be settled
+				let in_reverse_stage = match (Self::collateral_auctions(auction_id), Self::get_last_bid(*auction_id)) {
+					(Some(collateral_auction), Some((_, bid_price))) => collateral_auction.in_reverse_stage(bid_price),
+					_ => false,
+				};
+				if !in_reverse_stage {
 					return InvalidTransaction::Stale.into();
 				}
 
@@ -320,6 +946,11 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Get account of auction manager module, which holds the bids in escrow.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
 	fn get_last_bid(auction_id: AuctionId) -> Option<(T::AccountId, Balance)> {
 		T::Auction::auction_info(auction_id).and_then(|auction_info| auction_info.bid)
 	}
@@ -335,6 +966,17 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn submit_settle_reverse_auction_tx(auction_id: AuctionId) {
+		let call = Call::<T>::settle_reverse_auction(auction_id);
+		if let Err(err) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+			log::info!(
+				target: "auction-manager",
+				"offchain worker: submit unsigned auction settle tx for AuctionId {:?} failed: {:?}",
+				auction_id, err,
+			);
+		}
+	}
+
 	fn _offchain_worker() -> Result<(), OffchainErr> {
 		// acquire offchain worker lock.
 		let lock_expiration = Duration::from_millis(LOCK_DURATION);
@@ -359,7 +1001,7 @@ impl<T: Config> Pallet<T> {
 			max_iterations
 		);
 
-		// start iterations to cancel collateral auctions
+		// start iterations to cancel or settle collateral auctions
 		let mut iterator = match start_key {
 			Some(key) => <CollateralAuctions<T>>::iter_from(key),
 			None => <CollateralAuctions<T>>::iter(),
@@ -369,24 +1011,18 @@ impl<T: Config> Pallet<T> {
 		let mut finished = true;
 
 		#[allow(clippy::while_let_on_iterator)]
-		while let Some((collateral_auction_id, _)) = iterator.next() {
+		while let Some((collateral_auction_id, collateral_auction)) = iterator.next() {
 			iteration_count += 1;
 
-			if let (Some(collateral_auction), Some((_, last_bid_price))) = (
-				Self::collateral_auctions(collateral_auction_id),
-				Self::get_last_bid(collateral_auction_id),
-			) {
-				// if collateral auction has already been in reverse stage,
-				// should skip it.
-				if collateral_auction.in_reverse_stage(last_bid_price) {
-					if iteration_count == max_iterations {
-						finished = false;
-						break;
-					}
-					continue;
-				}
+			// if collateral auction has already been in reverse stage,
+			// should settle it to the last bidder.
+			if Self::get_last_bid(collateral_auction_id)
+				.map_or(false, |(_, last_bid_price)| collateral_auction.in_reverse_stage(last_bid_price))
+			{
+				Self::submit_settle_reverse_auction_tx(collateral_auction_id);
+			} else {
+				Self::submit_cancel_auction_tx(collateral_auction_id);
 			}
-			Self::submit_cancel_auction_tx(collateral_auction_id);
 
 			if iteration_count == max_iterations {
 				finished = false;
@@ -396,6 +1032,10 @@ impl<T: Config> Pallet<T> {
 		}
 
 		if finished {
+			// debit auctions are bounded by the lots created by CDP treasury, cancel them all at once
+			for debit_auction_id in <DebitAuctions<T>>::iter_keys() {
+				Self::submit_cancel_auction_tx(debit_auction_id);
+			}
 			to_be_continue.clear();
 		} else {
 			to_be_continue.set(&iterator.last_raw_key());
@@ -443,9 +1083,9 @@ impl<T: Config> Pallet<T> {
 		)?;
 
 		// if there's bid
-		if let Some((bidder, bid_price)) = last_bid {
-			// refund stable token to the bidder
-			T::CDPTreasury::issue_debit(&bidder, bid_price, false)?;
+		if let Some((bidder, _)) = last_bid {
+			// refund stable token to the bidder from escrow
+			Self::refund_escrowed_bid(id, &bidder)?;
 
 			// decrease account ref of bidder
 			frame_system::Pallet::<T>::dec_consumers(&bidder);
@@ -460,6 +1100,87 @@ impl<T: Config> Pallet<T> {
 		});
 		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
 
+		Self::archive_collateral_auction(
+			id,
+			&collateral_auction,
+			CollateralAuctionOutcome::Cancelled,
+			Zero::zero(),
+			Zero::zero(),
+			None,
+		);
+
+		Ok(())
+	}
+
+	/// Deal collateral auction `id` in reverse stage to the last bidder at the
+	/// price locked by the shutdown, the auction is removed without waiting for
+	/// its end. The last bidder pays the target held in escrow for the
+	/// collateral worth it at the locked price, but no more than the
+	/// collateral of its bid, and the rest is refunded to the refund
+	/// recipient.
+	fn do_settle_reverse_auction(id: AuctionId) -> DispatchResult {
+		let mut collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let (winner, bid_price) = Self::get_last_bid(id).ok_or(Error::<T>::NotInReverseStage)?;
+		ensure!(
+			collateral_auction.in_reverse_stage(bid_price),
+			Error::<T>::NotInReverseStage
+		);
+
+		// calculate how much collateral the payment is worth in settle price
+		let settle_price =
+			T::PriceSource::get_relative_price(T::GetSetUSDId::get(), collateral_auction.currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let payment_amount = collateral_auction.payment_amount(bid_price);
+		let settle_amount = sp_std::cmp::min(
+			settle_price.saturating_mul_int(payment_amount),
+			collateral_auction.amount,
+		);
+
+		let refund_collateral_amount = collateral_auction.amount.saturating_sub(settle_amount);
+		if !refund_collateral_amount.is_zero() {
+			T::CDPTreasury::withdraw_collateral(
+				&collateral_auction.refund_recipient,
+				collateral_auction.currency_id,
+				refund_collateral_amount,
+			)?;
+			TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+				*balance = balance.saturating_sub(refund_collateral_amount)
+			});
+			collateral_auction.amount = settle_amount;
+		}
+
+		<CollateralAuctions<T>>::remove(id);
+		CollateralAuctionRestarts::<T>::remove(id);
+		T::Auction::remove_auction(id);
+
+		Self::deal_collateral_auction(id, &collateral_auction, winner.clone());
+		Self::remove_collateral_auction_records(&collateral_auction);
+
+		// decrease account ref of winner
+		frame_system::Pallet::<T>::dec_consumers(&winner);
+
+		Self::deposit_event(Event::ReverseAuctionSettled {
+			auction_id: id,
+			winner,
+			collateral_amount: settle_amount,
+			payment_amount,
+			settle_price,
+		});
+		Ok(())
+	}
+
+	fn cancel_debit_auction(id: AuctionId, debit_auction: DebitAuctionItem<T::BlockNumber>) -> DispatchResult {
+		// if there's bid, refund stable token to the bidder from escrow
+		if let Some((bidder, _)) = Self::get_last_bid(id) {
+			Self::refund_escrowed_bid(id, &bidder)?;
+
+			// decrease account ref of bidder
+			frame_system::Pallet::<T>::dec_consumers(&bidder);
+		}
+
+		// decrease total debit and issuance in auction
+		Self::remove_debit_auction_records(&debit_auction);
+
 		Ok(())
 	}
 
@@ -484,43 +1205,314 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn get_minimum_increment_size(now: T::BlockNumber, start_block: T::BlockNumber) -> Rate {
-		if now >= start_block + T::AuctionDurationSoftCap::get() {
+	/// Get the auction duration soft cap of the collateral auction under
+	/// `currency_id`, or debit auction if `None`.
+	fn get_auction_duration_soft_cap(currency_id: Option<CurrencyId>) -> T::BlockNumber {
+		currency_id
+			.and_then(|currency_id| Self::collateral_auction_params(currency_id).auction_duration_soft_cap)
+			.unwrap_or_else(T::AuctionDurationSoftCap::get)
+	}
+
+	/// Get the auction duration hard cap of the collateral auction under
+	/// `currency_id`, or debit auction if `None`.
+	fn get_auction_duration_hard_cap(currency_id: Option<CurrencyId>) -> T::BlockNumber {
+		currency_id
+			.and_then(|currency_id| Self::collateral_auction_params(currency_id).auction_duration_hard_cap)
+			.unwrap_or_else(T::AuctionDurationHardCap::get)
+	}
+
+	/// Get the new end time of the auction after a bid accepted at `now`,
+	/// which is capped by the auction duration hard cap.
+	fn get_auction_end_time(
+		currency_id: Option<CurrencyId>,
+		now: T::BlockNumber,
+		start_block: T::BlockNumber,
+	) -> T::BlockNumber {
+		let deadline = start_block.saturating_add(Self::get_auction_duration_hard_cap(currency_id));
+		now.saturating_add(Self::get_auction_time_to_close(currency_id, now, start_block))
+			.min(deadline)
+			// the end time must be in the future, in case the hard cap is lowered during the auction
+			.max(now.saturating_add(One::one()))
+	}
+
+	/// Get the initial end time of the auction started at `start_block`.
+	fn get_auction_initial_end_time(currency_id: Option<CurrencyId>, start_block: T::BlockNumber) -> T::BlockNumber {
+		start_block.saturating_add(
+			Self::get_auction_duration_soft_cap(currency_id).min(Self::get_auction_duration_hard_cap(currency_id)),
+		)
+	}
+
+	fn get_minimum_increment_size(
+		currency_id: Option<CurrencyId>,
+		now: T::BlockNumber,
+		start_block: T::BlockNumber,
+	) -> Rate {
+		let minimum_increment_size = currency_id
+			.and_then(|currency_id| Self::collateral_auction_params(currency_id).minimum_increment_size)
+			.unwrap_or_else(T::MinimumIncrementSize::get);
+		if now >= start_block + Self::get_auction_duration_soft_cap(currency_id) {
 			// double the minimum increment size when reach soft cap
-			T::MinimumIncrementSize::get().saturating_mul(Rate::saturating_from_integer(2))
+			minimum_increment_size.saturating_mul(Rate::saturating_from_integer(2))
 		} else {
-			T::MinimumIncrementSize::get()
+			minimum_increment_size
 		}
 	}
 
-	fn get_auction_time_to_close(now: T::BlockNumber, start_block: T::BlockNumber) -> T::BlockNumber {
-		if now >= start_block + T::AuctionDurationSoftCap::get() {
+	fn get_auction_time_to_close(
+		currency_id: Option<CurrencyId>,
+		now: T::BlockNumber,
+		start_block: T::BlockNumber,
+	) -> T::BlockNumber {
+		let auction_time_to_close = currency_id
+			.and_then(|currency_id| Self::collateral_auction_params(currency_id).auction_time_to_close)
+			.unwrap_or_else(T::AuctionTimeToClose::get);
+		if now >= start_block + Self::get_auction_duration_soft_cap(currency_id) {
 			// halve the extended time of bid when reach soft cap
-			T::AuctionTimeToClose::get()
+			auction_time_to_close
 				.checked_div(&2u32.into())
 				.expect("cannot overflow with positive divisor; qed")
 		} else {
-			T::AuctionTimeToClose::get()
+			auction_time_to_close
 		}
 	}
 
-	/// Handles collateral auction new bid. Returns
-	/// `Ok(new_auction_end_time)` if bid accepted.
-	///
-	/// Ensured atomic.
-	#[transactional]
-	pub fn collateral_auction_bid_handler(
-		now: T::BlockNumber,
+	fn do_bid_with_loan(
+		who: &T::AccountId,
 		id: AuctionId,
-		new_bid: (T::AccountId, Balance),
-		last_bid: Option<(T::AccountId, Balance)>,
-	) -> sp_std::result::Result<T::BlockNumber, DispatchError> {
-		let (new_bidder, new_bid_price) = new_bid;
-		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+		bid_price: Balance,
+		debit_amount: Balance,
+	) -> DispatchResult {
+		ensure!(!debit_amount.is_zero(), Error::<T>::InvalidAmount);
+		let collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
 
-		<CollateralAuctions<T>>::try_mutate_exists(
+		// the position must back the new debit at the required ratio without the
+		// collateral to be won, so it stays valid whether or not the won collateral
+		// can be deposited at settlement
+		T::BidFunding::check_position_valid(who, collateral_auction.currency_id, Zero::zero(), debit_amount, true)?;
+
+		let last_bid = Self::place_collateral_auction_bid(
+			who,
 			id,
-			|collateral_auction| -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+			bid_price,
+			Some((collateral_auction.currency_id, debit_amount)),
+		)?;
+		Self::swap_bidders(who, last_bid.as_ref().map(|(last_bidder, _)| last_bidder));
+
+		Self::deposit_event(Event::LoanFundedBid {
+			auction_id: id,
+			bidder: who.clone(),
+			bid_price,
+			debit_amount,
+		});
+		Ok(())
+	}
+
+	/// Bid for multiple collateral auctions, each at the minimum acceptable
+	/// price not above its max price. Return the placed bids and the
+	/// auctions failed.
+	fn do_batch_bid(
+		who: &T::AccountId,
+		bids: Vec<(AuctionId, Balance)>,
+	) -> (Vec<(AuctionId, Balance)>, Vec<AuctionId>) {
+		let mut placed: Vec<(AuctionId, Balance)> = Vec::new();
+		let mut failed: Vec<AuctionId> = Vec::new();
+		let mut last_bidders: Vec<Option<T::AccountId>> = Vec::new();
+
+		for (id, max_price) in bids {
+			match Self::batch_bid_item(who, id, max_price) {
+				Ok((bid_price, last_bid)) => {
+					placed.push((id, bid_price));
+					last_bidders.push(last_bid.map(|(last_bidder, _)| last_bidder));
+				}
+				Err(_) => failed.push(id),
+			}
+		}
+
+		Self::batch_swap_bidders(who, last_bidders);
+		(placed, failed)
+	}
+
+	/// Bid for collateral auction `id` at the minimum acceptable price if it
+	/// does not exceed `max_price`. Return the bid price and the last bid.
+	#[allow(clippy::type_complexity)]
+	fn batch_bid_item(
+		who: &T::AccountId,
+		id: AuctionId,
+		max_price: Balance,
+	) -> sp_std::result::Result<(Balance, Option<(T::AccountId, Balance)>), DispatchError> {
+		let collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		let bid_price = Self::minimum_next_bid(
+			Self::get_last_bid(id).map_or(Zero::zero(), |(_, price)| price),
+			collateral_auction.target,
+			Self::get_minimum_increment_size(Some(collateral_auction.currency_id), now, collateral_auction.start_time),
+		);
+		ensure!(bid_price <= max_price, Error::<T>::InvalidBidPrice);
+
+		let last_bid = Self::place_collateral_auction_bid(who, id, bid_price, None)?;
+		Ok((bid_price, last_bid))
+	}
+
+	/// Place the bid of `who` for collateral auction `id` at `bid_price`
+	/// the way the `bid` of auction does, without updating the references of
+	/// the bidders. The payment is partly funded by minting `loan` debit
+	/// against the position of `who` if any. Return the last bid.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn place_collateral_auction_bid(
+		who: &T::AccountId,
+		id: AuctionId,
+		bid_price: Balance,
+		loan: Option<(CurrencyId, Balance)>,
+	) -> sp_std::result::Result<Option<(T::AccountId, Balance)>, DispatchError> {
+		let auction = T::Auction::auction_info(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(
+			auction.start <= now && auction.end.map_or(true, |end| end > now),
+			Error::<T>::AuctionNotActive
+		);
+		let last_bid_price = auction.bid.as_ref().map_or(Zero::zero(), |(_, price)| *price);
+		ensure!(bid_price > last_bid_price, Error::<T>::InvalidBidPrice);
+
+		// accept the bid by the same handler as `on_new_bid`, which checks the minimum
+		// increment and returns the extended end of the auction
+		let end = Self::collateral_auction_bid(now, id, (who.clone(), bid_price), auction.bid.clone(), loan)?;
+		T::Auction::update_auction(
+			id,
+			AuctionInfo {
+				bid: Some((who.clone(), bid_price)),
+				start: auction.start,
+				end: Some(end),
+			},
+		)?;
+
+		Self::deposit_event(Event::CollateralAuctionBid {
+			auction_id: id,
+			bidder: who.clone(),
+			bid_price,
+		});
+		Ok(auction.bid)
+	}
+
+	/// Return the minimum bid price to be accepted by the next bid.
+	fn minimum_next_bid(
+		last_bid_price: Balance,
+		target_price: Balance,
+		minimum_increment: Rate,
+	) -> Balance {
+		minimum_increment
+			.saturating_mul_int(sp_std::cmp::max(target_price, last_bid_price))
+			.saturating_add(last_bid_price)
+			.max(One::one())
+	}
+
+	/// Return the payment amount and the collateral amount for sale if
+	/// collateral auction `id` is bid at `bid_price` now.
+	pub fn collateral_auction_amounts_at_bid(id: AuctionId, bid_price: Balance) -> Option<(Balance, Balance)> {
+		let collateral_auction = Self::collateral_auctions(id)?;
+		let last_bid_price = Self::get_last_bid(id).map_or(Zero::zero(), |(_, price)| price);
+		Some((
+			collateral_auction.payment_amount(bid_price),
+			collateral_auction.collateral_amount(last_bid_price, bid_price),
+		))
+	}
+
+	/// Return the live state of collateral auction `id`, `None` if it does
+	/// not exist.
+	pub fn collateral_auction_details(id: AuctionId) -> Option<CollateralAuctionDetails<T::AccountId, T::BlockNumber>> {
+		let collateral_auction = Self::collateral_auctions(id)?;
+		let auction_info = T::Auction::auction_info(id)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		let last_bid_price = auction_info.bid.as_ref().map_or(Zero::zero(), |(_, price)| *price);
+
+		let minimum_next_bid = Self::minimum_next_bid(
+			last_bid_price,
+			collateral_auction.target,
+			Self::get_minimum_increment_size(Some(collateral_auction.currency_id), now, collateral_auction.start_time),
+		);
+		// the collateral price implied by the minimum next bid in stable currency
+		let oracle_discount =
+			T::PriceSource::get_relative_price(collateral_auction.currency_id, T::GetSetUSDId::get()).and_then(
+				|oracle_price| {
+					let implied_price = Rate::checked_from_rational(
+						collateral_auction.payment_amount(minimum_next_bid),
+						collateral_auction.collateral_amount(last_bid_price, minimum_next_bid),
+					)?;
+					Some(Rate::one().saturating_sub(implied_price.checked_div(&oracle_price)?))
+				},
+			);
+
+		Some(CollateralAuctionDetails {
+			auction_id: id,
+			collateral_type: collateral_auction.currency_id,
+			refund_recipient: collateral_auction.refund_recipient.clone(),
+			amount: collateral_auction.amount,
+			target: collateral_auction.target,
+			start_time: collateral_auction.start_time,
+			end_time: auction_info.end,
+			in_reverse_stage: collateral_auction.in_reverse_stage(last_bid_price),
+			last_bid: auction_info.bid,
+			minimum_next_bid,
+			oracle_discount,
+		})
+	}
+
+	/// Return the live state of all active collateral auctions.
+	pub fn active_collateral_auctions() -> Vec<CollateralAuctionDetails<T::AccountId, T::BlockNumber>> {
+		<CollateralAuctions<T>>::iter_keys()
+			.filter_map(Self::collateral_auction_details)
+			.collect()
+	}
+
+	/// Return the archived records of finalized collateral auctions, from the
+	/// oldest to the latest.
+	pub fn settled_collateral_auctions() -> Vec<SettledCollateralAuction<T::AccountId, T::BlockNumber>> {
+		let max_archived = u64::from(T::MaxArchivedCollateralAuctions::get());
+		if max_archived.is_zero() {
+			return Vec::new();
+		}
+		let count = Self::archived_collateral_auction_count();
+		(count.saturating_sub(max_archived)..count)
+			.filter_map(|index| ArchivedCollateralAuctionIds::<T>::get((index % max_archived) as u32))
+			.filter_map(Self::settled_collateral_auction)
+			.collect()
+	}
+
+	/// Handles collateral auction new bid. Returns
+	/// `Ok(new_auction_end_time)` if bid accepted.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn collateral_auction_bid_handler(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+		let new_bidder = new_bid.0.clone();
+		let last_bidder = last_bid.as_ref().map(|(who, _)| who.clone());
+		let new_auction_end_time = Self::collateral_auction_bid(now, id, new_bid, last_bid, None)?;
+		Self::swap_bidders(&new_bidder, last_bidder.as_ref());
+		Ok(new_auction_end_time)
+	}
+
+	/// Accept collateral auction new bid without updating the references of
+	/// the bidders, the payment is partly funded by minting `loan` debit if
+	/// any. Returns `Ok(new_auction_end_time)` if bid accepted.
+	fn collateral_auction_bid(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+		loan: Option<(CurrencyId, Balance)>,
+	) -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+		let (new_bidder, new_bid_price) = new_bid;
+		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+
+		<CollateralAuctions<T>>::try_mutate_exists(
+			id,
+			|collateral_auction| -> sp_std::result::Result<T::BlockNumber, DispatchError> {
 				let mut collateral_auction = collateral_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
 				let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price); // get last bid price
 
@@ -530,29 +1522,25 @@ impl<T: Config> Pallet<T> {
 						new_bid_price,
 						last_bid_price,
 						collateral_auction.target,
-						Self::get_minimum_increment_size(now, collateral_auction.start_time),
+						Self::get_minimum_increment_size(
+							Some(collateral_auction.currency_id),
+							now,
+							collateral_auction.start_time,
+						),
 					),
 					Error::<T>::InvalidBidPrice
 				);
 
 				let last_bidder = last_bid.as_ref().map(|(who, _)| who);
 
-				let mut payment = collateral_auction.payment_amount(new_bid_price);
-
-				// if there's bid before, return stablecoin from new bidder to last bidder
-				if let Some(last_bidder) = last_bidder {
-					let refund = collateral_auction.payment_amount(last_bid_price);
-					T::Currency::transfer(T::GetSetUSDId::get(), &new_bidder, last_bidder, refund)?;
-
-					payment = payment
-						.checked_sub(refund)
-						// This should never fail because new bid payment are always greater or equal to last bid
-						// payment.
-						.ok_or(Error::<T>::InvalidBidPrice)?;
-				}
-
-				// transfer remain payment from new bidder to CDP treasury
-				T::CDPTreasury::deposit_surplus(&new_bidder, payment)?;
+				// hold the payment of new bidder in escrow, and refund the last bidder from escrow
+				Self::escrow_bid(
+					id,
+					&new_bidder,
+					collateral_auction.payment_amount(new_bid_price),
+					last_bidder,
+					loan,
+				)?;
 
 				// if collateral auction will be in reverse stage, refund collateral to it's
 				// origin from auction CDP treasury
@@ -575,40 +1563,151 @@ impl<T: Config> Pallet<T> {
 					}
 				}
 
+				Ok(Self::get_auction_end_time(
+					Some(collateral_auction.currency_id),
+					now,
+					collateral_auction.start_time,
+				))
+			},
+		)
+	}
+
+	/// Handles debit auction new bid. Returns `Ok(new_auction_end_time)` if
+	/// bid accepted.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn debit_auction_bid_handler(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+		let (new_bidder, new_bid_price) = new_bid;
+		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+
+		<DebitAuctions<T>>::try_mutate_exists(
+			id,
+			|debit_auction| -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+				let mut debit_auction = debit_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
+				let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price); // get last bid price
+
+				// ensure new bid price is valid
+				ensure!(
+					new_bid_price >= debit_auction.fix
+						&& Self::check_minimum_increment(
+							new_bid_price,
+							last_bid_price,
+							debit_auction.fix,
+							Self::get_minimum_increment_size(None, now, debit_auction.start_time),
+						),
+					Error::<T>::InvalidBidPrice
+				);
+
+				let last_bidder = last_bid.as_ref().map(|(who, _)| who);
+
+				// hold the fix payment of new bidder in escrow, and refund the last bidder from escrow
+				Self::escrow_bid(id, &new_bidder, debit_auction.fix, last_bidder, None)?;
+
 				Self::swap_bidders(&new_bidder, last_bidder);
 
-				Ok(now + Self::get_auction_time_to_close(now, collateral_auction.start_time))
+				// the higher bid price, the less issuance currency for sale
+				debit_auction.amount = debit_auction.amount_for_sale(last_bid_price, new_bid_price);
+
+				Ok(Self::get_auction_end_time(None, now, debit_auction.start_time))
 			},
 		)
 	}
 
+	fn debit_auction_end_handler(
+		auction_id: AuctionId,
+		debit_auction: DebitAuctionItem<T::BlockNumber>,
+		last_bid: Option<(T::AccountId, Balance)>,
+	) {
+		if let Some((winner, _)) = last_bid {
+			Self::try_release_escrowed_bid(auction_id);
+
+			// If failed, just the winner did not get the issuance currency. It can be fixed by treasury council.
+			let res = T::SerpTreasury::issue_standard(debit_auction.currency_id, &winner, debit_auction.amount);
+			if let Err(e) = res {
+				log::warn!(
+					target: "auction-manager",
+					"issue_standard: failed to issue {:?} {:?} to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					debit_auction.amount, debit_auction.currency_id, winner, e
+				);
+				debug_assert!(false);
+			}
+
+			Self::deposit_event(Event::DebitAuctionDealt {
+				auction_id,
+				issuance_currency_id: debit_auction.currency_id,
+				issuance_amount: debit_auction.amount,
+				winner,
+				payment_amount: debit_auction.fix,
+			});
+		} else {
+			// abort this debit auction, the debit can be reprocessed by cdp treasury.
+			Self::deposit_event(Event::DebitAuctionAborted {
+				auction_id,
+				issuance_currency_id: debit_auction.currency_id,
+				initial_amount: debit_auction.initial_amount,
+				fix_debit_amount: debit_auction.fix,
+			});
+		}
+
+		Self::remove_debit_auction_records(&debit_auction);
+	}
+
+	fn remove_debit_auction_records(debit_auction: &DebitAuctionItem<T::BlockNumber>) {
+		TotalDebitInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(debit_auction.fix));
+		TotalIssuanceInDebitAuction::<T>::mutate(debit_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(debit_auction.initial_amount)
+		});
+	}
+
 	fn collateral_auction_end_handler(
 		auction_id: AuctionId,
 		collateral_auction: CollateralAuctionItem<T::AccountId, T::BlockNumber>,
 		last_bid: Option<(T::AccountId, Balance)>,
 	) {
+		let restarts = CollateralAuctionRestarts::<T>::take(auction_id);
 		let (last_bidder, bid_price) = if let Some((bidder, bid_price)) = last_bid.clone() {
 			(Some(bidder), bid_price)
 		} else {
 			(None, Zero::zero())
 		};
 
+		let return_excess_proceeds = Self::collateral_auction_params(collateral_auction.currency_id)
+			.proceeds_distribution
+			!= Some(ProceedsDistribution::RefundCollateral);
 		let swap_limit = if collateral_auction.always_forward() {
 			SwapLimit::ExactSupply(collateral_auction.amount, bid_price)
+		} else if return_excess_proceeds {
+			SwapLimit::ExactSupply(collateral_auction.amount, collateral_auction.target)
 		} else {
 			SwapLimit::ExactTarget(collateral_auction.amount, collateral_auction.target)
 		};
 
-		// if DEX give a price no less than the last_bidder for swap target
-		if let Ok((actual_supply_amount, actual_target_amount)) =
-			T::CDPTreasury::swap_collateral_to_stable(collateral_auction.currency_id, swap_limit, true)
+		// if DEX give a price no less than the last_bidder for swap target, and the price is within the band
+		if let Some((actual_supply_amount, actual_target_amount)) =
+			Self::try_dex_take(auction_id, collateral_auction.currency_id, swap_limit)
 		{
 			Self::try_refund_collateral(
 				collateral_auction.currency_id,
 				&collateral_auction.refund_recipient,
 				collateral_auction.amount.saturating_sub(actual_supply_amount),
 			);
-			Self::try_refund_bid(&collateral_auction, last_bid);
+			Self::try_refund_bid(auction_id, last_bid);
+			Self::distribute_proceeds(auction_id, &collateral_auction, actual_target_amount);
+			Self::archive_collateral_auction(
+				auction_id,
+				&collateral_auction,
+				CollateralAuctionOutcome::DEXTake,
+				actual_supply_amount,
+				actual_target_amount,
+				None,
+			);
 
 			Self::deposit_event(Event::DEXTakeCollateralAuction {
 				auction_id,
@@ -620,20 +1719,40 @@ impl<T: Config> Pallet<T> {
 		} else if last_bidder.is_some() && bid_price >= collateral_auction.target {
 			// if these's bid which is gte target, auction should dealt by the last bidder.
 			let winner = last_bidder.expect("ensured last bidder not empty; qed");
+			Self::deal_collateral_auction(auction_id, &collateral_auction, winner);
+		} else if let Some((new_auction_id, new_target)) =
+			Self::try_restart_collateral_auction(auction_id, &collateral_auction, restarts)
+		{
+			// the collateral is re-listed with a lower target, refund the bid held in escrow.
+			Self::try_refund_bid(auction_id, last_bid);
+			Self::archive_collateral_auction(
+				auction_id,
+				&collateral_auction,
+				CollateralAuctionOutcome::Restarted(new_auction_id),
+				Zero::zero(),
+				Zero::zero(),
+				None,
+			);
 
-			Self::try_refund_collateral(collateral_auction.currency_id, &winner, collateral_auction.amount);
-			let payment_amount = collateral_auction.payment_amount(bid_price);
-
-			Self::deposit_event(Event::CollateralAuctionDealt {
+			Self::deposit_event(Event::CollateralAuctionRestarted {
 				auction_id,
+				new_auction_id,
 				collateral_type: collateral_auction.currency_id,
 				collateral_amount: collateral_auction.amount,
-				winner,
-				payment_amount,
+				target_stable_amount: collateral_auction.target,
+				new_target_stable_amount: new_target,
 			});
 		} else {
 			// abort this collateral auction, these collateral can be reprocessed by cdp treausry.
-			Self::try_refund_bid(&collateral_auction, last_bid);
+			Self::try_refund_bid(auction_id, last_bid);
+			Self::archive_collateral_auction(
+				auction_id,
+				&collateral_auction,
+				CollateralAuctionOutcome::Aborted,
+				Zero::zero(),
+				Zero::zero(),
+				None,
+			);
 
 			Self::deposit_event(Event::CollateralAuctionAborted {
 				auction_id,
@@ -644,6 +1763,49 @@ impl<T: Config> Pallet<T> {
 			});
 		}
 
+		Self::remove_collateral_auction_records(&collateral_auction);
+	}
+
+	/// Deal the collateral auction to the winner, the payment held in escrow
+	/// is released to CDP treasury.
+	fn deal_collateral_auction(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		winner: T::AccountId,
+	) {
+		if let Some((_, _, _, _, mint_fee)) =
+			LoanFundedBids::<T>::take(auction_id).filter(|(bidder, ..)| *bidder == winner)
+		{
+			Self::try_deposit_won_collateral(
+				&winner,
+				collateral_auction.currency_id,
+				collateral_auction.amount,
+				mint_fee,
+			);
+		} else {
+			Self::try_refund_collateral(collateral_auction.currency_id, &winner, collateral_auction.amount);
+		}
+		let payment_amount = Self::try_release_escrowed_bid(auction_id);
+		Self::distribute_proceeds(auction_id, collateral_auction, payment_amount);
+		Self::archive_collateral_auction(
+			auction_id,
+			collateral_auction,
+			CollateralAuctionOutcome::Dealt,
+			collateral_auction.amount,
+			payment_amount,
+			Some(winner.clone()),
+		);
+
+		Self::deposit_event(Event::CollateralAuctionDealt {
+			auction_id,
+			collateral_type: collateral_auction.currency_id,
+			collateral_amount: collateral_auction.amount,
+			winner,
+			payment_amount,
+		});
+	}
+
+	fn remove_collateral_auction_records(collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>) {
 		// decrement recipient account reference
 		frame_system::Pallet::<T>::dec_consumers(&collateral_auction.refund_recipient);
 
@@ -654,20 +1816,470 @@ impl<T: Config> Pallet<T> {
 		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
 	}
 
-	// Refund stable to the last_bidder.
-	fn try_refund_bid(
+	/// Take the collateral auction by DEX, return the actual supply and target
+	/// amount if the swap succeeded within the price band.
+	fn try_dex_take(
+		auction_id: AuctionId,
+		currency_id: CurrencyId,
+		swap_limit: SwapLimit<Balance>,
+	) -> Option<(Balance, Balance)> {
+		match Self::dex_take(currency_id, swap_limit) {
+			Ok(result) => Some(result),
+			Err(e) => {
+				if e == Error::<T>::DEXPriceDeviated.into() {
+					Self::deposit_event(Event::DEXTakeRejected {
+						auction_id,
+						collateral_type: currency_id,
+					});
+				}
+				None
+			}
+		}
+	}
+
+	/// Swap the collateral in auction to stable currency by DEX, revert the
+	/// swap if the price deviates from the oracle or average price beyond
+	/// `dex_take_max_deviation`.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn dex_take(
+		currency_id: CurrencyId,
+		swap_limit: SwapLimit<Balance>,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let (supply_amount, target_amount) = T::CDPTreasury::swap_collateral_to_stable(currency_id, swap_limit, true)?;
+
+		let max_deviation = Self::collateral_auction_params(currency_id).dex_take_max_deviation;
+		let min_price_ratio = Ratio::one().saturating_sub(max_deviation.unwrap_or_default());
+		if max_deviation.is_some() {
+			let oracle_price = T::PriceSource::get_relative_price(currency_id, T::GetSetUSDId::get())
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			ensure!(
+				target_amount >= min_price_ratio.saturating_mul_int(oracle_price.saturating_mul_int(supply_amount)),
+				Error::<T>::DEXPriceDeviated
+			);
+		}
+
+		// the average price only exists when its period is set
+		if let Some((average_price, _)) = Self::dex_average_price(currency_id) {
+			ensure!(
+				target_amount >= min_price_ratio.saturating_mul_int(average_price.saturating_mul_int(supply_amount)),
+				Error::<T>::DEXPriceDeviated
+			);
+		}
+
+		Ok((supply_amount, target_amount))
+	}
+
+	/// Move the exponential moving average DEX prices of the collateral types
+	/// which enable the check towards the current DEX prices of selling their
+	/// collateral in auction. The average moves by the share of the blocks
+	/// elapsed since its last update in the period, and takes the current
+	/// price once a full period has elapsed. Return the number of collateral
+	/// types iterated, each charged with a DEX quote.
+	fn update_dex_average_prices(now: T::BlockNumber) -> u32 {
+		let mut count: u32 = 0;
+		for (currency_id, params) in CollateralAuctionParameters::<T>::iter() {
+			count = count.saturating_add(1);
+			let period = match params.dex_average_price_period {
+				Some(period) => period,
+				None => continue,
+			};
+
+			// quote selling the collateral in auction by the path DEX takes it with, nothing
+			// to quote if there is no collateral in auction
+			let dex_price = match T::CDPTreasury::get_collateral_swap_amount(
+				currency_id,
+				SwapLimit::ExactSupply(Self::total_collateral_in_auction(currency_id), Zero::zero()),
+			)
+			.and_then(|(supply_amount, target_amount)| Price::checked_from_rational(target_amount, supply_amount))
+			{
+				Some(price) => price,
+				None => continue,
+			};
+
+			let average_price = match Self::dex_average_price(currency_id) {
+				Some((average_price, updated_at)) => {
+					let elapsed: u128 = now.saturating_sub(updated_at).unique_saturated_into();
+					let period: u128 = period.unique_saturated_into();
+					let weight = Ratio::checked_from_rational(elapsed, period)
+						.unwrap_or_else(Ratio::one)
+						.min(Ratio::one());
+					if dex_price >= average_price {
+						average_price.saturating_add(weight.saturating_mul(dex_price.saturating_sub(average_price)))
+					} else {
+						average_price.saturating_sub(weight.saturating_mul(average_price.saturating_sub(dex_price)))
+					}
+				}
+				None => dex_price,
+			};
+			DEXAveragePrices::<T>::insert(currency_id, (average_price, now));
+		}
+		count
+	}
+
+	/// Record the proceeds of collateral auction covering the debt plus
+	/// penalty owed as recovered, and return the rest to the refund
+	/// recipient. It is owed nothing by the auctions always forward, so their
+	/// proceeds are returned in full.
+	fn distribute_proceeds(
+		auction_id: AuctionId,
 		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
-		last_bid: Option<(T::AccountId, Balance)>,
+		proceeds: Balance,
+	) {
+		// only the proceeds covering the debt owed recover the bad debt and realise
+		// the liquidation penalty
+		T::CDPTreasury::on_auction_proceeds(
+			collateral_auction.currency_id,
+			&collateral_auction.refund_recipient,
+			collateral_auction.debt_owed,
+			proceeds,
+		);
+
+		let mut excess_amount = proceeds.saturating_sub(collateral_auction.debt_owed);
+
+		if !excess_amount.is_zero() {
+			// If failed, the excess proceeds are kept by cdp treasury. It can be fixed by treasury council.
+			if let Err(e) = T::CDPTreasury::return_excess_proceeds(
+				collateral_auction.currency_id,
+				&collateral_auction.refund_recipient,
+				excess_amount,
+			) {
+				log::warn!(
+					target: "auction-manager",
+					"return_excess_proceeds: failed to return {:?} of auction {:?} to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					excess_amount, auction_id, collateral_auction.refund_recipient, e
+				);
+				excess_amount = Zero::zero();
+			}
+		}
+
+		Self::deposit_event(Event::CollateralAuctionProceedsDistributed {
+			auction_id,
+			collateral_type: collateral_auction.currency_id,
+			proceeds,
+			covered_amount: proceeds.saturating_sub(excess_amount),
+			excess_amount,
+			refund_recipient: collateral_auction.refund_recipient.clone(),
+		});
+	}
+
+	/// Archive the finalized collateral auction and update the statistics of
+	/// its collateral type, prune the oldest record if the archive is full.
+	fn archive_collateral_auction(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		outcome: CollateralAuctionOutcome,
+		sold_amount: Balance,
+		proceeds: Balance,
+		winner: Option<T::AccountId>,
 	) {
-		if let Some((bidder, bid_price)) = last_bid {
+		let oracle_price = T::PriceSource::get_relative_price(collateral_auction.currency_id, T::GetSetUSDId::get());
+
+		CollateralAuctionStatistics::<T>::mutate(collateral_auction.currency_id, |stats| {
+			match outcome {
+				CollateralAuctionOutcome::Dealt => stats.dealt_count = stats.dealt_count.saturating_add(1),
+				CollateralAuctionOutcome::DEXTake => stats.dex_take_count = stats.dex_take_count.saturating_add(1),
+				CollateralAuctionOutcome::Restarted(_) => {
+					stats.restarted_count = stats.restarted_count.saturating_add(1)
+				}
+				CollateralAuctionOutcome::Aborted => stats.aborted_count = stats.aborted_count.saturating_add(1),
+				CollateralAuctionOutcome::Cancelled => stats.cancelled_count = stats.cancelled_count.saturating_add(1),
+			}
+
+			stats.sold_amount = stats.sold_amount.saturating_add(sold_amount);
+			stats.proceeds = stats.proceeds.saturating_add(proceeds);
+			if let Some(price) = oracle_price {
+				if !sold_amount.is_zero() {
+					stats.oracle_value = stats.oracle_value.saturating_add(price.saturating_mul_int(sold_amount));
+					stats.priced_proceeds = stats.priced_proceeds.saturating_add(proceeds);
+				}
+			}
+
+			// the target of the auction re-listed is carried over by the new auction, and
+			// the one of the auction cancelled is settled by cdp treasury.
+			let settled = matches!(
+				outcome,
+				CollateralAuctionOutcome::Dealt | CollateralAuctionOutcome::DEXTake | CollateralAuctionOutcome::Aborted
+			);
+			if settled && !collateral_auction.always_forward() {
+				stats.target = stats.target.saturating_add(collateral_auction.target);
+				stats.recovered = stats
+					.recovered
+					.saturating_add(sp_std::cmp::min(proceeds, collateral_auction.target));
+			}
+			stats.update_rates();
+		});
+
+		let max_archived = T::MaxArchivedCollateralAuctions::get();
+		if max_archived.is_zero() {
+			return;
+		}
+		let count = Self::archived_collateral_auction_count();
+		let slot = (count % u64::from(max_archived)) as u32;
+		if let Some(pruned_id) = ArchivedCollateralAuctionIds::<T>::get(slot) {
+			SettledCollateralAuctions::<T>::remove(pruned_id);
+		}
+		ArchivedCollateralAuctionIds::<T>::insert(slot, auction_id);
+		ArchivedCollateralAuctionCount::<T>::put(count.saturating_add(1));
+
+		SettledCollateralAuctions::<T>::insert(
+			auction_id,
+			SettledCollateralAuction {
+				auction_id,
+				collateral_type: collateral_auction.currency_id,
+				outcome,
+				refund_recipient: collateral_auction.refund_recipient.clone(),
+				collateral_amount: collateral_auction.amount,
+				target: collateral_auction.target,
+				sold_amount,
+				proceeds,
+				winner,
+				settlement_price: Price::checked_from_rational(proceeds, sold_amount),
+				oracle_price,
+				start_time: collateral_auction.start_time,
+				end_time: <frame_system::Pallet<T>>::block_number(),
+			},
+		);
+	}
+
+	/// Re-list the collateral of the auction which ended without reaching the
+	/// target according to the restart policy of the collateral type, return
+	/// the new auction id and target if restarted.
+	fn try_restart_collateral_auction(
+		auction_id: AuctionId,
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		restarts: u32,
+	) -> Option<(AuctionId, Balance)> {
+		if T::EmergencyShutdown::is_shutdown() {
+			return None;
+		}
+		let policy = Self::collateral_auction_params(collateral_auction.currency_id).restart_policy?;
+		if restarts >= policy.max_restarts {
+			return None;
+		}
+
+		let new_target = collateral_auction
+			.target
+			.saturating_sub(policy.target_discount.saturating_mul_int(collateral_auction.target));
+		match Self::do_new_collateral_auction(
+			&collateral_auction.refund_recipient,
+			collateral_auction.currency_id,
+			collateral_auction.amount,
+			new_target,
+			collateral_auction.debt_owed,
+		) {
+			Ok(new_auction_id) => {
+				CollateralAuctionRestarts::<T>::insert(new_auction_id, restarts.saturating_add(1));
+				Some((new_auction_id, new_target))
+			}
+			Err(e) => {
+				// If failed, the auction is aborted and the collateral can be reprocessed by cdp treasury.
+				log::warn!(
+					target: "auction-manager",
+					"new_collateral_auction: failed to restart collateral auction {:?}: {:?}. \
+					This is unexpected but should be safe",
+					auction_id, e
+				);
+				None
+			}
+		}
+	}
+
+	/// Create a new collateral auction covering `debt_owed` of the refund
+	/// recipient, return the auction id.
+	fn do_new_collateral_auction(
+		refund_recipient: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		target: Balance,
+		debt_owed: Balance,
+	) -> sp_std::result::Result<AuctionId, DispatchError> {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+		TotalCollateralInAuction::<T>::try_mutate(currency_id, |total| -> DispatchResult {
+			*total = total.checked_add(amount).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		if !target.is_zero() {
+			// no-op if target is zero
+			TotalTargetInAuction::<T>::try_mutate(|total| -> DispatchResult {
+				*total = total.checked_add(target).ok_or(Error::<T>::InvalidAmount)?;
+				Ok(())
+			})?;
+		}
+
+		let start_time = <frame_system::Pallet<T>>::block_number();
+		// use start_time + auction duration soft cap as the initial end-time of collateral auction,
+		// which is no later than the hard cap.
+		let end_time = Self::get_auction_initial_end_time(Some(currency_id), start_time);
+		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+
+		<CollateralAuctions<T>>::insert(
+			auction_id,
+			CollateralAuctionItem {
+				refund_recipient: refund_recipient.clone(),
+				currency_id,
+				initial_amount: amount,
+				amount,
+				target,
+				debt_owed,
+				start_time,
+			},
+		);
+
+		// increment recipient account reference
+		if frame_system::Pallet::<T>::inc_consumers(refund_recipient).is_err() {
+			// No providers for the locks. This is impossible under normal circumstances
+			// since the funds that are under the lock will themselves be stored in the
+			// account and therefore will need a reference.
+			log::warn!(
+				target: "auction-manager",
+				"Attempt to `inc_consumers` for {:?} failed. \
+				This is unexpected but should be safe.",
+				refund_recipient.clone()
+			);
+		}
+
+		Self::deposit_event(Event::NewCollateralAuction {
+			auction_id,
+			collateral_type: currency_id,
+			collateral_amount: amount,
+			target_bid_price: target,
+		});
+		Ok(auction_id)
+	}
+
+	/// Refund the payment of `last_bidder` held in escrow, and hold `payment`
+	/// of `new_bidder` in escrow. If `loan` is given, the debit is minted
+	/// against the position of `new_bidder` straight into escrow and its value
+	/// must not exceed `payment`, the rest and the mint fee charged if the bid
+	/// wins are transferred from `new_bidder`.
+	fn escrow_bid(
+		id: AuctionId,
+		new_bidder: &T::AccountId,
+		payment: Balance,
+		last_bidder: Option<&T::AccountId>,
+		loan: Option<(CurrencyId, Balance)>,
+	) -> DispatchResult {
+		if let Some(last_bidder) = last_bidder {
+			Self::refund_escrowed_bid(id, last_bidder)?;
+		}
+
+		let (loan_value, mint_fee) = match loan {
+			Some((collateral_type, debit_amount)) => {
+				let (debit_value, mint_fee) = T::BidFunding::borrow_for_bid(
+					new_bidder,
+					collateral_type,
+					debit_amount,
+					T::GetSetUSDId::get(),
+					&Self::account_id(),
+				)?;
+				ensure!(debit_value <= payment, Error::<T>::InvalidAmount);
+				LoanFundedBids::<T>::insert(
+					id,
+					(new_bidder.clone(), collateral_type, debit_amount, debit_value, mint_fee),
+				);
+				(debit_value, mint_fee)
+			}
+			None => (Zero::zero(), Zero::zero()),
+		};
+
+		T::Currency::transfer(
+			T::GetSetUSDId::get(),
+			new_bidder,
+			&Self::account_id(),
+			payment.saturating_sub(loan_value).saturating_add(mint_fee),
+		)?;
+		BidEscrows::<T>::insert(id, payment);
+		Ok(())
+	}
+
+	/// Refund the payment held in escrow for auction `id` to `bidder`. If the
+	/// bid was funded by minting debit, the debit is repaid from the escrow
+	/// first and only the rest is refunded, along with the mint fee held.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn refund_escrowed_bid(id: AuctionId, bidder: &T::AccountId) -> DispatchResult {
+		let mut refund_amount = BidEscrows::<T>::take(id);
+
+		if let Some((loan_bidder, collateral_type, debit_amount, debit_value, mint_fee)) = LoanFundedBids::<T>::take(id)
+		{
+			T::BidFunding::repay_bid(
+				&loan_bidder,
+				collateral_type,
+				debit_amount,
+				debit_value,
+				&Self::account_id(),
+			)?;
+			refund_amount = refund_amount.saturating_sub(debit_value).saturating_add(mint_fee);
+		}
+
+		T::Currency::transfer(T::GetSetUSDId::get(), &Self::account_id(), bidder, refund_amount)
+	}
+
+	/// Deposit the collateral won by a bid funded by minting debit into the
+	/// position of the winner and charge the mint fee held in escrow. The
+	/// position was checked to back the debit without the won collateral when
+	/// the bid was placed.
+	fn try_deposit_won_collateral(
+		winner: &T::AccountId,
+		collateral_type: CurrencyId,
+		amount: Balance,
+		mint_fee: Balance,
+	) {
+		if let Err(e) =
+			T::BidFunding::deposit_won_collateral(winner, collateral_type, amount, mint_fee, &Self::account_id())
+		{
+			// If failed, the winner get the collateral and the mint fee directly, the position still backs
+			// its debit as checked when the bid was placed.
+			log::warn!(
+				target: "auction-manager",
+				"deposit_won_collateral: failed to deposit {:?} {:?} into the position of {:?}: {:?}. \
+				This is unexpected but should be safe",
+				amount, collateral_type, winner, e
+			);
+			Self::try_refund_collateral(collateral_type, winner, amount);
+			if let Err(e) = T::Currency::transfer(T::GetSetUSDId::get(), &Self::account_id(), winner, mint_fee) {
+				log::warn!(
+					target: "auction-manager",
+					"transfer: failed to refund mint fee {:?} to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					mint_fee, winner, e
+				);
+			}
+		}
+	}
+
+	/// Release the payment held in escrow for auction `id` to CDP treasury as
+	/// surplus, return the released amount.
+	fn try_release_escrowed_bid(id: AuctionId) -> Balance {
+		let escrowed = BidEscrows::<T>::take(id);
+		// If failed, the payment is kept by auction manager. It can be fixed by treasury council.
+		if let Err(e) = T::CDPTreasury::deposit_surplus(&Self::account_id(), escrowed) {
+			log::warn!(
+				target: "auction-manager",
+				"deposit_surplus: failed to release escrowed bid {:?} of auction {:?}: {:?}. \
+				This is unexpected but should be safe",
+				escrowed, id, e
+			);
+			debug_assert!(false);
+		}
+		escrowed
+	}
+
+	// Refund stable held in escrow to the last_bidder.
+	fn try_refund_bid(id: AuctionId, last_bid: Option<(T::AccountId, Balance)>) {
+		if let Some((bidder, _)) = last_bid {
 			// If failed, just the bid did not get the stable. It can be fixed by treasury council.
-			let res = T::CDPTreasury::issue_debit(&bidder, collateral_auction.payment_amount(bid_price), false);
-			if let Err(e) = res {
+			if let Err(e) = Self::refund_escrowed_bid(id, &bidder) {
 				log::warn!(
 					target: "auction-manager",
-					"issue_debit: failed to issue stable {:?} to {:?}: {:?}. \
+					"refund_escrowed_bid: failed to refund escrowed bid of auction {:?} to {:?}: {:?}. \
 					This is unexpected but should be safe",
-					collateral_auction.payment_amount(bid_price), bidder, e
+					id, bidder, e
 				);
 				debug_assert!(false);
 			}
@@ -692,6 +2304,37 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// increment `new_bidder` reference once for each bid placed and
+	/// decrement the references of `last_bidders` in one pass, the bids
+	/// replacing the own bids of `new_bidder` keep its reference unchanged.
+	fn batch_swap_bidders(new_bidder: &T::AccountId, last_bidders: Vec<Option<T::AccountId>>) {
+		let mut increments: u32 = 0;
+		for last_bidder in last_bidders {
+			match last_bidder {
+				Some(who) if who == *new_bidder => {}
+				Some(who) => {
+					increments = increments.saturating_add(1);
+					frame_system::Pallet::<T>::dec_consumers(&who);
+				}
+				None => increments = increments.saturating_add(1),
+			}
+		}
+
+		for _ in 0..increments {
+			if frame_system::Pallet::<T>::inc_consumers(new_bidder).is_err() {
+				// No providers for the locks. This is impossible under normal circumstances
+				// since the funds that are under the lock will themselves be stored in the
+				// account and therefore will need a reference.
+				log::warn!(
+					target: "auction-manager",
+					"inc_consumers: failed for {:?}. \
+					This is impossible under normal circumstances.",
+					new_bidder.clone()
+				);
+			}
+		}
+	}
+
 	/// increment `new_bidder` reference and decrement `last_bidder`
 	/// reference if any
 	fn swap_bidders(new_bidder: &T::AccountId, last_bidder: Option<&T::AccountId>) {
@@ -720,7 +2363,11 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 		new_bid: (T::AccountId, Balance),
 		last_bid: Option<(T::AccountId, Balance)>,
 	) -> OnNewBidResult<T::BlockNumber> {
-		let bid_result = Self::collateral_auction_bid_handler(now, id, new_bid, last_bid);
+		let bid_result = if <DebitAuctions<T>>::contains_key(id) {
+			Self::debit_auction_bid_handler(now, id, new_bid, last_bid)
+		} else {
+			Self::collateral_auction_bid_handler(now, id, new_bid, last_bid)
+		};
 
 		match bid_result {
 			Ok(new_auction_end_time) => OnNewBidResult {
@@ -737,6 +2384,8 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
 			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
+		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
+			Self::debit_auction_end_handler(id, debit_auction, winner.clone());
 		}
 
 		if let Some((bidder, _)) = &winner {
@@ -757,71 +2406,76 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 		amount: Self::Balance,
 		target: Self::Balance,
 	) -> DispatchResult {
-		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
-		TotalCollateralInAuction::<T>::try_mutate(currency_id, |total| -> DispatchResult {
-			*total = total.checked_add(amount).ok_or(Error::<T>::InvalidAmount)?;
-			Ok(())
-		})?;
+		Self::do_new_collateral_auction(refund_recipient, currency_id, amount, target, target).map(|_| ())
+	}
 
-		if !target.is_zero() {
-			// no-op if target is zero
-			TotalTargetInAuction::<T>::try_mutate(|total| -> DispatchResult {
-				*total = total.checked_add(target).ok_or(Error::<T>::InvalidAmount)?;
-				Ok(())
-			})?;
+	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
+		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
+			Self::cancel_collateral_auction(id, collateral_auction)?;
+			CollateralAuctionRestarts::<T>::remove(id);
+		} else {
+			let debit_auction = <DebitAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
+			Self::cancel_debit_auction(id, debit_auction)?;
 		}
+		T::Auction::remove_auction(id);
+		Ok(())
+	}
+
+	fn get_total_collateral_in_auction(id: Self::CurrencyId) -> Self::Balance {
+		Self::total_collateral_in_auction(id)
+	}
+
+	fn get_total_target_in_auction() -> Self::Balance {
+		Self::total_target_in_auction()
+	}
+}
+
+impl<T: Config> cdp_treasury::DebitAuctionManager for Pallet<T> {
+	fn new_debit_auction(currency_id: CurrencyId, initial_amount: Balance, fix: Balance) -> DispatchResult {
+		ensure!(
+			!initial_amount.is_zero() && !fix.is_zero(),
+			Error::<T>::InvalidAmount,
+		);
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+		TotalDebitInAuction::<T>::try_mutate(|total| -> DispatchResult {
+			*total = total.checked_add(fix).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+		TotalIssuanceInDebitAuction::<T>::try_mutate(currency_id, |total| -> DispatchResult {
+			*total = total.checked_add(initial_amount).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
 
 		let start_time = <frame_system::Pallet<T>>::block_number();
-		// use start_time + AuctionDurationSoftCap as the initial end-time of collateral auction.
-		let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
+		// use start_time + auction duration soft cap as the initial end-time of debit auction.
+		let end_time = Self::get_auction_initial_end_time(None, start_time);
 		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
 
-		<CollateralAuctions<T>>::insert(
+		<DebitAuctions<T>>::insert(
 			auction_id,
-			CollateralAuctionItem {
-				refund_recipient: refund_recipient.clone(),
+			DebitAuctionItem {
 				currency_id,
-				initial_amount: amount,
-				amount,
-				target,
+				initial_amount,
+				amount: initial_amount,
+				fix,
 				start_time,
 			},
 		);
 
-		// increment recipient account reference
-		if frame_system::Pallet::<T>::inc_consumers(refund_recipient).is_err() {
-			// No providers for the locks. This is impossible under normal circumstances
-			// since the funds that are under the lock will themselves be stored in the
-			// account and therefore will need a reference.
-			log::warn!(
-				target: "auction-manager",
-				"Attempt to `inc_consumers` for {:?} failed. \
-				This is unexpected but should be safe.",
-				refund_recipient.clone()
-			);
-		}
-
-		Self::deposit_event(Event::NewCollateralAuction {
+		Self::deposit_event(Event::NewDebitAuction {
 			auction_id,
-			collateral_type: currency_id,
-			collateral_amount: amount,
-			target_bid_price: target,
+			issuance_currency_id: currency_id,
+			initial_amount,
+			fix_debit_amount: fix,
 		});
 		Ok(())
 	}
 
-	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
-		let collateral_auction = <CollateralAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
-		Self::cancel_collateral_auction(id, collateral_auction)?;
-		T::Auction::remove_auction(id);
-		Ok(())
+	fn get_total_debit_in_auction() -> Balance {
+		Self::total_debit_in_auction()
 	}
 
-	fn get_total_collateral_in_auction(id: Self::CurrencyId) -> Self::Balance {
-		Self::total_collateral_in_auction(id)
-	}
-
-	fn get_total_target_in_auction() -> Self::Balance {
-		Self::total_target_in_auction()
+	fn get_total_issuance_in_auction(currency_id: CurrencyId) -> Balance {
+		Self::total_issuance_in_debit_auction(currency_id)
 	}
 }
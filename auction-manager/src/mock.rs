@@ -20,6 +20,7 @@ use sp_runtime::{
 	traits::{IdentityLookup, One as OneT},
 };
 use sp_std::cell::RefCell;
+use std::collections::HashMap;
 pub use support::{Price, SerpTreasury};
 
 pub type AccountId = u128;
@@ -30,6 +31,7 @@ pub type Amount = i64;
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CAROL: AccountId = 3;
+pub const LOANS: AccountId = 10;
 pub const SETR: CurrencyId = CurrencyId::Token(TokenSymbol::SETR);
 pub const SETUSD: CurrencyId = CurrencyId::Token(TokenSymbol::SETUSD);
 pub const SERP: CurrencyId = CurrencyId::Token(TokenSymbol::SERP);
@@ -198,11 +200,11 @@ impl SerpTreasury<AccountId> for MockSerpTreasury {
 
 	/// issue standard to `who`
 	fn issue_standard(
-		_currency_id: CurrencyId,
-		_who: &AccountId,
-		_standard: Balance
+		currency_id: CurrencyId,
+		who: &AccountId,
+		standard: Balance
 	) -> DispatchResult {
-		unimplemented!()
+		Tokens::deposit(currency_id, who, standard)
 	}
 
 	/// burn standard(stable currency) of `who`
@@ -255,7 +257,9 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetSetUSDId: CurrencyId = SETUSD;
 	pub const MaxAuctionsCount: u32 = 10_000;
+	pub const BalanceSheetSnapshotCapacity: u32 = 10;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");
+	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![SERP, DNAR];
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![DNAR],
 	];
@@ -265,13 +269,19 @@ impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
 	type GetSetUSDId = GetSetUSDId;
+	type StableCurrencyIds = StableCurrencyIds;
 	type AuctionManagerHandler = AuctionManagerModule;
+	type DebitAuctionManagerHandler = AuctionManagerModule;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
+	type PriceSource = MockPriceSource;
+	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type SerpTreasury = MockSerpTreasury;
 	type PalletId = CDPTreasuryPalletId;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type BalanceSheetSnapshotCapacity = BalanceSheetSnapshotCapacity;
 	type WeightInfo = ();
 }
 
@@ -339,11 +349,95 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+thread_local! {
+	static POSITIONS: RefCell<HashMap<AccountId, (Balance, Balance)>> = RefCell::new(HashMap::new());
+}
+
+/// Return the mock position of `who` as (collateral, debit).
+pub fn mock_position(who: AccountId) -> (Balance, Balance) {
+	POSITIONS.with(|v| v.borrow().get(&who).cloned().unwrap_or_default())
+}
+
+pub fn set_mock_position(who: AccountId, collateral: Balance, debit: Balance) {
+	POSITIONS.with(|v| v.borrow_mut().insert(who, (collateral, debit)));
+}
+
+thread_local! {
+	static MINT_FEE_RATE: RefCell<Rate> = RefCell::new(Rate::zero());
+}
+
+pub fn set_mock_mint_fee_rate(rate: Rate) {
+	MINT_FEE_RATE.with(|v| *v.borrow_mut() = rate);
+}
+
+// mock bid funding, mint debit value 1:1 at the mock mint fee rate and require the collateral no less than the debit
+pub struct MockBidFunding;
+impl AuctionBidFunding<AccountId> for MockBidFunding {
+	fn borrow_for_bid(
+		who: &AccountId,
+		_currency_id: CurrencyId,
+		debit_amount: Balance,
+		stable_currency_id: CurrencyId,
+		to: &AccountId,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Tokens::deposit(stable_currency_id, to, debit_amount)?;
+		let (collateral, debit) = mock_position(*who);
+		set_mock_position(*who, collateral, debit + debit_amount);
+		Ok((debit_amount, MINT_FEE_RATE.with(|v| v.borrow().saturating_mul_int(debit_amount))))
+	}
+
+	fn repay_bid(
+		who: &AccountId,
+		_currency_id: CurrencyId,
+		debit_amount: Balance,
+		debit_value: Balance,
+		from: &AccountId,
+	) -> DispatchResult {
+		Tokens::withdraw(SETUSD, from, debit_value)?;
+		let (collateral, debit) = mock_position(*who);
+		set_mock_position(*who, collateral, debit - debit_amount);
+		Ok(())
+	}
+
+	fn deposit_won_collateral(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		mint_fee: Balance,
+		from: &AccountId,
+	) -> DispatchResult {
+		CDPTreasuryModule::withdraw_collateral(&LOANS, currency_id, amount)?;
+		CDPTreasuryModule::deposit_surplus(from, mint_fee)?;
+		let (collateral, debit) = mock_position(*who);
+		set_mock_position(*who, collateral + amount, debit);
+		Ok(())
+	}
+
+	fn check_position_valid(
+		who: &AccountId,
+		_currency_id: CurrencyId,
+		collateral_increase: Balance,
+		debit_increase: Balance,
+		_check_required_ratio: bool,
+	) -> DispatchResult {
+		let (collateral, debit) = mock_position(*who);
+		if collateral + collateral_increase >= debit + debit_increase {
+			Ok(())
+		} else {
+			Err(DispatchError::Other("mock below liquidation ratio error"))
+		}
+	}
+}
+
 parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(1, 20);
 	pub const AuctionTimeToClose: u64 = 100;
 	pub const AuctionDurationSoftCap: u64 = 2000;
+	pub const AuctionDurationHardCap: u64 = 5000;
+	pub const MaxBatchBids: u32 = 3;
+	pub const MaxArchivedCollateralAuctions: u32 = 2;
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const AuctionManagerPalletId: PalletId = PalletId(*b"set/aucm");
 	pub DefaultSwapParitalPathList: Vec<Vec<CurrencyId>> = vec![
 		vec![SETUSD],
 		vec![DNAR, SETUSD],
@@ -357,12 +451,19 @@ impl Config for Runtime {
 	type MinimumIncrementSize = MinimumIncrementSize;
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
+	type AuctionDurationHardCap = AuctionDurationHardCap;
 	type GetSetUSDId = GetSetUSDId;
 	type CDPTreasury = CDPTreasuryModule;
 	type DEX = DEXModule;
 	type PriceSource = MockPriceSource;
+	type SerpTreasury = MockSerpTreasury;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type PalletId = AuctionManagerPalletId;
+	type MaxBatchBids = MaxBatchBids;
+	type MaxArchivedCollateralAuctions = MaxArchivedCollateralAuctions;
+	type BidFunding = MockBidFunding;
 	type WeightInfo = ();
 }
 
@@ -10,11 +10,12 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use cdp_treasury::DebitAuctionManager;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use mock::{Call as MockCall, Event, *};
 use sp_core::offchain::{testing, DbExternalities, OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt};
 use sp_io::offchain;
-use sp_runtime::traits::One;
+use sp_runtime::traits::{BadOrigin, One};
 
 fn run_to_block_offchain(n: u64) {
 	while System::block_number() < n {
@@ -28,8 +29,125 @@ fn run_to_block_offchain(n: u64) {
 #[test]
 fn get_auction_time_to_close_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(AuctionManagerModule::get_auction_time_to_close(2000, 1), 100);
-		assert_eq!(AuctionManagerModule::get_auction_time_to_close(2001, 1), 50);
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(None, 2000, 1), 100);
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(None, 2001, 1), 50);
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(Some(SERP), 2001, 1), 50);
+	});
+}
+
+#[test]
+fn set_collateral_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = CollateralAuctionParams {
+			minimum_increment_size: Some(Rate::saturating_from_rational(1, 10)),
+			auction_time_to_close: Some(20),
+			auction_duration_soft_cap: Some(500),
+			auction_duration_hard_cap: None,
+			restart_policy: None,
+			proceeds_distribution: None,
+			dex_take_max_deviation: None,
+			dex_average_price_period: None,
+		};
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_params(Origin::signed(BOB), SERP, params.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_params(
+				Origin::signed(ALICE),
+				SERP,
+				CollateralAuctionParams {
+					auction_time_to_close: Some(0),
+					..Default::default()
+				}
+			),
+			Error::<Runtime>::InvalidAuctionParams
+		);
+
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			params.clone()
+		));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionParamsUpdated {
+			collateral_type: SERP,
+			new_params: params.clone(),
+		}));
+		assert_eq!(AuctionManagerModule::collateral_auction_params(SERP), params);
+
+		// the params of SERP are used, the others fall back to constants
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(Some(SERP), 500, 1), 20);
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(Some(SERP), 501, 1), 10);
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(Some(DNAR), 501, 1), 100);
+		assert_eq!(AuctionManagerModule::get_auction_time_to_close(None, 501, 1), 100);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(Some(SERP), 500, 1),
+			Rate::saturating_from_rational(1, 10)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(Some(SERP), 501, 1),
+			Rate::saturating_from_rational(2, 10)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_minimum_increment_size(Some(DNAR), 501, 1),
+			Rate::saturating_from_rational(1, 20)
+		);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, SERP, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 10, 100));
+		assert_eq!(AuctionModule::auctions(0).unwrap().end, Some(501));
+		assert_noop!(
+			AuctionManagerModule::collateral_auction_bid_handler(2, 0, (BOB, 9), None),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(2, 0, (BOB, 10), None),
+			Ok(22)
+		);
+	});
+}
+
+#[test]
+fn collateral_auction_hard_cap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_params(
+				Origin::signed(ALICE),
+				SERP,
+				CollateralAuctionParams {
+					auction_duration_hard_cap: Some(1000),
+					..Default::default()
+				}
+			),
+			Error::<Runtime>::InvalidAuctionParams
+		);
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			CollateralAuctionParams {
+				auction_duration_hard_cap: Some(2100),
+				..Default::default()
+			}
+		));
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, SERP, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 10, 100));
+		assert_eq!(AuctionModule::auctions(0).unwrap().end, Some(2001));
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(2050, 0, (BOB, 10), None),
+			Ok(2100)
+		);
+		// bids can not extend the auction beyond the hard cap
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(2060, 0, (CAROL, 20), Some((BOB, 10))),
+			Ok(2101)
+		);
+
+		// debit auctions fall back to the constant hard cap
+		assert_eq!(AuctionManagerModule::get_auction_end_time(None, 4990, 1), 5001);
+		assert_eq!(AuctionManagerModule::get_auction_end_time(None, 5001, 1), 5002);
 	});
 }
 
@@ -67,6 +185,63 @@ fn collateral_auction_methods() {
 	});
 }
 
+#[test]
+fn collateral_auction_details_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(AuctionManagerModule::collateral_auction_details(0), None);
+		assert_eq!(AuctionManagerModule::collateral_auction_amounts_at_bid(0, 100), None);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, SERP, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 10, 100));
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_details(0),
+			Some(CollateralAuctionDetails {
+				auction_id: 0,
+				collateral_type: SERP,
+				refund_recipient: ALICE,
+				amount: 10,
+				target: 100,
+				start_time: 1,
+				end_time: Some(2001),
+				last_bid: None,
+				in_reverse_stage: false,
+				minimum_next_bid: 5,
+				oracle_discount: Some(Rate::saturating_from_rational(1, 2)),
+			})
+		);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 100));
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_details(0),
+			Some(CollateralAuctionDetails {
+				auction_id: 0,
+				collateral_type: SERP,
+				refund_recipient: ALICE,
+				amount: 10,
+				target: 100,
+				start_time: 1,
+				end_time: Some(101),
+				last_bid: Some((BOB, 100)),
+				in_reverse_stage: true,
+				minimum_next_bid: 105,
+				oracle_discount: Some(Rate::zero()),
+			})
+		);
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_amounts_at_bid(0, 200),
+			Some((100, 5))
+		);
+
+		MockPriceSource::set_relative_price(None);
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_details(0).map(|details| details.oracle_discount),
+			Some(None)
+		);
+		assert_eq!(AuctionManagerModule::active_collateral_auctions().len(), 1);
+	});
+}
+
 #[test]
 fn new_collateral_auction_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -97,6 +272,43 @@ fn new_collateral_auction_work() {
 	});
 }
 
+#[test]
+fn migrate_collateral_auctions_without_debt_owed() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::new(0).put::<AuctionManagerModule>();
+		frame_support::storage::unhashed::put(
+			&CollateralAuctions::<Runtime>::hashed_key_for(0),
+			&OldCollateralAuctionItem {
+				refund_recipient: ALICE,
+				currency_id: SERP,
+				initial_amount: 10,
+				amount: 8,
+				target: 100,
+				start_time: 1,
+			},
+		);
+
+		AuctionManagerModule::on_runtime_upgrade();
+		assert_eq!(
+			AuctionManagerModule::collateral_auctions(0),
+			Some(CollateralAuctionItem {
+				refund_recipient: ALICE,
+				currency_id: SERP,
+				initial_amount: 10,
+				amount: 8,
+				target: 100,
+				debt_owed: 100,
+				start_time: 1,
+			})
+		);
+		assert_eq!(StorageVersion::get::<AuctionManagerModule>(), 1);
+
+		// running it again leaves the migrated auctions untouched
+		AuctionManagerModule::on_runtime_upgrade();
+		assert_eq!(AuctionManagerModule::collateral_auctions(0).map(|item| item.debt_owed), Some(100));
+	});
+}
+
 #[test]
 fn collateral_auction_bid_handler_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -117,7 +329,9 @@ fn collateral_auction_bid_handler_work() {
 			Error::<Runtime>::InvalidBidPrice,
 		);
 		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 5), None).is_ok(),);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 5);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 5);
+		assert_eq!(Tokens::free_balance(SETUSD, &AuctionManagerModule::account_id()), 5);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 995);
 
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -125,7 +339,9 @@ fn collateral_auction_bid_handler_work() {
 		let carol_ref_count_0 = System::consumers(&CAROL);
 
 		assert!(AuctionManagerModule::collateral_auction_bid_handler(2, 0, (CAROL, 10), Some((BOB, 5))).is_ok(),);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 10);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 10);
+		assert_eq!(Tokens::free_balance(SETUSD, &AuctionManagerModule::account_id()), 10);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(SETUSD, &CAROL), 990);
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).unwrap().amount, 10);
@@ -136,7 +352,9 @@ fn collateral_auction_bid_handler_work() {
 		assert_eq!(carol_ref_count_1, carol_ref_count_0 + 1);
 
 		assert!(AuctionManagerModule::collateral_auction_bid_handler(3, 0, (BOB, 200), Some((CAROL, 10))).is_ok(),);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 100);
+		assert_eq!(Tokens::free_balance(SETUSD, &AuctionManagerModule::account_id()), 100);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 900);
 		assert_eq!(Tokens::free_balance(SETUSD, &CAROL), 1000);
 		assert_eq!(AuctionManagerModule::collateral_auctions(0).unwrap().amount, 5);
@@ -255,7 +473,8 @@ fn always_forward_collateral_auction_dealt() {
 		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 200), None).is_ok());
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 100);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
 		assert_eq!(Tokens::free_balance(SERP, &BOB), 1000);
 		let ref_count_0 = System::consumers(&CDPTreasuryModule::account_id());
 		let bob_ref_count_0 = System::consumers(&BOB);
@@ -271,6 +490,9 @@ fn always_forward_collateral_auction_dealt() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 0);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
+		assert_eq!(Tokens::free_balance(SETUSD, &AuctionManagerModule::account_id()), 0);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 200);
 		assert_eq!(Tokens::free_balance(SERP, &BOB), 1100);
 		let ref_count_1 = System::consumers(&CDPTreasuryModule::account_id());
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -279,6 +501,33 @@ fn always_forward_collateral_auction_dealt() {
 	});
 }
 
+#[test]
+fn always_forward_collateral_auction_return_proceeds_to_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 0));
+		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 200), None).is_ok());
+
+		// nothing is owed by the owner, all the proceeds are returned
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 200)));
+		System::assert_has_event(Event::AuctionManagerModule(
+			crate::Event::CollateralAuctionProceedsDistributed {
+				auction_id: 0,
+				collateral_type: SERP,
+				proceeds: 200,
+				covered_amount: 0,
+				excess_amount: 200,
+				refund_recipient: ALICE,
+			},
+		));
+		assert_eq!(Tokens::free_balance(SERP, &BOB), 1100);
+		assert_eq!(Tokens::free_balance(SETUSD, &ALICE), 1200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 0);
+	});
+}
+
 #[test]
 fn always_forward_collateral_auction_with_bid_taked_by_dex() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -303,7 +552,8 @@ fn always_forward_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 100);
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (100, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 500);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 500);
 		let ref_count_0 = System::consumers(&CDPTreasuryModule::account_id());
@@ -321,8 +571,9 @@ fn always_forward_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 0);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (200, 500));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 500);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
 		let ref_count_1 = System::consumers(&CDPTreasuryModule::account_id());
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -344,13 +595,22 @@ fn reverse_collateral_auction_with_bid_taked_by_dex() {
 			1000,
 			0
 		));
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			CollateralAuctionParams {
+				proceeds_distribution: Some(ProceedsDistribution::RefundCollateral),
+				..Default::default()
+			}
+		));
 
 		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
 		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 200), None).is_ok());
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 100);
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (100, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
 		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1000);
@@ -368,8 +628,8 @@ fn reverse_collateral_auction_with_bid_taked_by_dex() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 0);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (126, 806));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 400);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1074);
 		let bob_ref_count_1 = System::consumers(&BOB);
@@ -377,6 +637,192 @@ fn reverse_collateral_auction_with_bid_taked_by_dex() {
 	});
 }
 
+#[test]
+fn collateral_auction_taked_by_dex_return_excess_proceeds() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SERP,
+			SETUSD,
+			100,
+			1000,
+			0
+		));
+
+		// the excess proceeds are returned by default
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 200), None).is_ok());
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 200)));
+
+		let (proceeds, covered_amount, excess_amount) = System::events()
+			.into_iter()
+			.find_map(|record| match record.event {
+				Event::AuctionManagerModule(crate::Event::CollateralAuctionProceedsDistributed {
+					auction_id: 0,
+					proceeds,
+					covered_amount,
+					excess_amount,
+					..
+				}) => Some((proceeds, covered_amount, excess_amount)),
+				_ => None,
+			})
+			.unwrap();
+		assert!(proceeds > 200);
+		assert_eq!(covered_amount, 200);
+		assert_eq!(excess_amount, proceeds - 200);
+
+		// all the collateral is sold, and the excess proceeds are returned to the refund recipient
+		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 0);
+		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1000);
+		assert_eq!(Tokens::free_balance(SETUSD, &ALICE), 1000 + excess_amount);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 200);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+	});
+}
+
+#[test]
+fn dex_take_rejected_when_deviate_from_oracle_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SERP,
+			SETUSD,
+			100,
+			1000,
+			0
+		));
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			CollateralAuctionParams {
+				dex_take_max_deviation: Some(Ratio::saturating_from_rational(1, 10)),
+				..Default::default()
+			}
+		));
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(20)));
+
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 200), None).is_ok());
+
+		// the DEX price is far below the oracle price, fall back to the bidder
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 200)));
+		System::assert_has_event(Event::AuctionManagerModule(crate::Event::DEXTakeRejected {
+			auction_id: 0,
+			collateral_type: SERP,
+		}));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionDealt {
+			auction_id: 0,
+			collateral_type: SERP,
+			collateral_amount: 100,
+			winner: BOB,
+			payment_amount: 200,
+		}));
+		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (100, 1000));
+		assert_eq!(Tokens::free_balance(SERP, &BOB), 1100);
+	});
+}
+
+#[test]
+fn dex_take_rejected_when_deviate_from_average_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SERP,
+			SETUSD,
+			100,
+			1000,
+			0
+		));
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_params(
+				Origin::signed(ALICE),
+				SERP,
+				CollateralAuctionParams {
+					dex_average_price_period: Some(0),
+					..Default::default()
+				}
+			),
+			Error::<Runtime>::InvalidAuctionParams
+		);
+		// the average price is checked without the oracle deviation
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			CollateralAuctionParams {
+				dex_average_price_period: Some(10),
+				..Default::default()
+			}
+		));
+
+		// nothing to quote without collateral in auction, the quote is still charged
+		assert_eq!(
+			AuctionManagerModule::on_initialize(1),
+			<() as WeightInfo>::on_initialize(1)
+		);
+		assert_eq!(AuctionManagerModule::dex_average_price(SERP), None);
+
+		// the average price is quoted by selling the collateral in auction
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 20));
+		AuctionManagerModule::on_initialize(1);
+		assert_eq!(
+			AuctionManagerModule::dex_average_price(SERP),
+			Some((Price::saturating_from_integer(5), 1))
+		);
+		AuctionManagerModule::on_initialize(6);
+		assert_eq!(
+			AuctionManagerModule::dex_average_price(SERP),
+			Some((Price::saturating_from_integer(5), 6))
+		);
+
+		// the pool is manipulated in the block ending the auction
+		assert_ok!(DEXModule::swap_with_exact_supply(
+			Origin::signed(CAROL),
+			vec![SERP, SETUSD],
+			300,
+			0
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (400, 250));
+
+		AuctionManagerModule::on_auction_ended(0, None);
+		System::assert_has_event(Event::AuctionManagerModule(crate::Event::DEXTakeRejected {
+			auction_id: 0,
+			collateral_type: SERP,
+		}));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionAborted {
+			auction_id: 0,
+			collateral_type: SERP,
+			collateral_amount: 100,
+			target_stable_amount: 20,
+			refund_recipient: ALICE,
+		}));
+		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (400, 250));
+		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
+
+		// the average price moves towards the DEX price by the elapsed time
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 20));
+		AuctionManagerModule::on_initialize(11);
+		assert_eq!(
+			AuctionManagerModule::dex_average_price(SERP),
+			Some((Price::saturating_from_rational(275, 100), 11))
+		);
+
+		// disable the check
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			Default::default()
+		));
+		assert_eq!(AuctionManagerModule::dex_average_price(SERP), None);
+	});
+}
+
 #[test]
 fn reverse_collateral_auction_with_bid_dealt() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -386,7 +832,8 @@ fn reverse_collateral_auction_with_bid_dealt() {
 		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 250), None).is_ok());
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 80);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 80);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(SERP, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
@@ -432,7 +879,8 @@ fn collateral_auction_with_bid_aborted() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 100);
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (500, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 180);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 180);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 820);
 		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1000);
@@ -450,8 +898,9 @@ fn collateral_auction_with_bid_aborted() {
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (500, 1000));
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 180);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 180);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
 		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1000);
 		let alice_ref_count_1 = System::consumers(&ALICE);
@@ -460,15 +909,349 @@ fn collateral_auction_with_bid_aborted() {
 }
 
 #[test]
-fn swap_bidders_works() {
+fn collateral_auction_with_bid_restarted() {
 	ExtBuilder::default().build().execute_with(|| {
-		let alice_ref_count_0 = System::consumers(&ALICE);
-		let bob_ref_count_0 = System::consumers(&BOB);
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SERP,
+			SETUSD,
+			500,
+			1000,
+			0
+		));
+		assert_noop!(
+			AuctionManagerModule::set_collateral_auction_params(
+				Origin::signed(ALICE),
+				SERP,
+				CollateralAuctionParams {
+					restart_policy: Some(CollateralAuctionRestartPolicy {
+						target_discount: Rate::saturating_from_rational(11, 10),
+						max_restarts: 1,
+					}),
+					..Default::default()
+				}
+			),
+			Error::<Runtime>::InvalidAuctionParams
+		);
+		assert_ok!(AuctionManagerModule::set_collateral_auction_params(
+			Origin::signed(ALICE),
+			SERP,
+			CollateralAuctionParams {
+				restart_policy: Some(CollateralAuctionRestartPolicy {
+					target_discount: Rate::saturating_from_rational(1, 10),
+					max_restarts: 1,
+				}),
+				..Default::default()
+			}
+		));
 
-		AuctionManagerModule::swap_bidders(&BOB, None);
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 180), None).is_ok());
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 820);
+		let alice_ref_count_0 = System::consumers(&ALICE);
 
-		let bob_ref_count_1 = System::consumers(&BOB);
-		assert_eq!(bob_ref_count_1, bob_ref_count_0 + 1);
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 180)));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionRestarted {
+			auction_id: 0,
+			new_auction_id: 1,
+			collateral_type: SERP,
+			collateral_amount: 100,
+			target_stable_amount: 200,
+			new_target_stable_amount: 180,
+		}));
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+		let restarted_auction = AuctionManagerModule::collateral_auctions(1).unwrap();
+		assert_eq!(restarted_auction.amount, 100);
+		assert_eq!(restarted_auction.target, 180);
+		assert_eq!(restarted_auction.debt_owed, 200);
+		assert_eq!(restarted_auction.refund_recipient, ALICE);
+		assert_eq!(AuctionModule::auctions(1).unwrap().end, Some(2001));
+		assert_eq!(AuctionManagerModule::collateral_auction_restarts(1), 1);
+		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 100);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 180);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0);
+
+		// abort the auction when reach the max restarts
+		AuctionManagerModule::on_auction_ended(1, None);
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionAborted {
+			auction_id: 1,
+			collateral_type: SERP,
+			collateral_amount: 100,
+			target_stable_amount: 180,
+			refund_recipient: ALICE,
+		}));
+		assert_eq!(AuctionManagerModule::collateral_auction_restarts(1), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn settled_collateral_auction_archive_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 300));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert!(AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 250), None).is_ok());
+		assert_eq!(AuctionManagerModule::settled_collateral_auction(0), None);
+		assert_eq!(AuctionManagerModule::collateral_auction_stats(SERP), Default::default());
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(4)));
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 250)));
+		assert_eq!(
+			AuctionManagerModule::settled_collateral_auction(0),
+			Some(SettledCollateralAuction {
+				auction_id: 0,
+				collateral_type: SERP,
+				outcome: CollateralAuctionOutcome::Dealt,
+				refund_recipient: ALICE,
+				collateral_amount: 80,
+				target: 200,
+				sold_amount: 80,
+				proceeds: 200,
+				winner: Some(BOB),
+				settlement_price: Some(Price::saturating_from_rational(5, 2)),
+				oracle_price: Some(Price::saturating_from_integer(4)),
+				start_time: 1,
+				end_time: 1,
+			})
+		);
+
+		System::set_block_number(2);
+		AuctionManagerModule::on_auction_ended(1, None);
+		let record = AuctionManagerModule::settled_collateral_auction(1).unwrap();
+		assert_eq!(record.outcome, CollateralAuctionOutcome::Aborted);
+		assert_eq!(record.sold_amount, 0);
+		assert_eq!(record.settlement_price, None);
+		assert_eq!(record.end_time, 2);
+
+		let stats = AuctionManagerModule::collateral_auction_stats(SERP);
+		assert_eq!(stats.dealt_count, 1);
+		assert_eq!(stats.aborted_count, 1);
+		assert_eq!(stats.sold_amount, 80);
+		assert_eq!(stats.proceeds, 200);
+		assert_eq!(stats.oracle_value, 320);
+		assert_eq!(stats.target, 400);
+		assert_eq!(stats.recovered, 200);
+		assert_eq!(stats.average_oracle_discount, Some(Rate::saturating_from_rational(3, 8)));
+		assert_eq!(stats.recovery_rate, Some(Ratio::saturating_from_rational(1, 2)));
+
+		// the oldest record is pruned when the archive is full
+		mock_shutdown();
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 2));
+		assert_eq!(AuctionManagerModule::archived_collateral_auction_count(), 3);
+		assert_eq!(AuctionManagerModule::settled_collateral_auction(0), None);
+		assert_eq!(
+			AuctionManagerModule::settled_collateral_auctions()
+				.iter()
+				.map(|record| (record.auction_id, record.outcome))
+				.collect::<Vec<_>>(),
+			vec![(1, CollateralAuctionOutcome::Aborted), (2, CollateralAuctionOutcome::Cancelled)]
+		);
+		let stats = AuctionManagerModule::collateral_auction_stats(SERP);
+		assert_eq!(stats.cancelled_count, 1);
+		assert_eq!(stats.target, 400);
+	});
+}
+
+#[test]
+fn bid_with_loan_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		set_mock_position(BOB, 150, 0);
+
+		assert_noop!(
+			AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 100, 0),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 1, 100, 100),
+			Error::<Runtime>::AuctionNotExists,
+		);
+		// the collateral to be won is not counted before settlement
+		assert_noop!(
+			AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 100, 200),
+			DispatchError::Other("mock below liquidation ratio error"),
+		);
+		// the debit value can not exceed the payment
+		assert_noop!(
+			AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 100, 120),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		// the minted debit value is held in escrow directly
+		assert_ok!(AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 100, 100));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::LoanFundedBid {
+			auction_id: 0,
+			bidder: BOB,
+			bid_price: 100,
+			debit_amount: 100,
+		}));
+		assert_eq!(AuctionModule::auctions(0).unwrap().bid, Some((BOB, 100)));
+		assert_eq!(AuctionModule::auctions(0).unwrap().end, Some(101));
+		assert_eq!(AuctionManagerModule::loan_funded_bid(0), Some((BOB, SERP, 100, 100, 0)));
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 100);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+		assert_eq!(mock_position(BOB), (150, 100));
+		assert_noop!(
+			AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 100, 100),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		// the debit is repaid from escrow when outbid
+		assert_ok!(AuctionModule::bid(Origin::signed(CAROL), 0, 150));
+		assert_eq!(AuctionManagerModule::loan_funded_bid(0), None);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 150);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+		assert_eq!(mock_position(BOB), (150, 0));
+
+		// the minimum increment applies as to the bid of auction
+		assert_noop!(
+			AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 155, 100),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		// the payment not funded by the debit is paid by the bidder
+		System::set_block_number(50);
+		assert_ok!(AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 200, 150));
+		System::assert_has_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionBid {
+			auction_id: 0,
+			bidder: BOB,
+			bid_price: 200,
+		}));
+		assert_eq!(AuctionModule::auctions(0).unwrap().end, Some(150));
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 950);
+		assert_eq!(Tokens::free_balance(SETUSD, &CAROL), 1000);
+		assert_eq!(mock_position(BOB), (150, 150));
+
+		// the won collateral is deposited into the position
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 200)));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionDealt {
+			auction_id: 0,
+			collateral_type: SERP,
+			collateral_amount: 100,
+			winner: BOB,
+			payment_amount: 200,
+		}));
+		assert_eq!(AuctionManagerModule::loan_funded_bid(0), None);
+		assert_eq!(mock_position(BOB), (250, 150));
+		assert_eq!(Tokens::free_balance(SERP, &LOANS), 100);
+		assert_eq!(Tokens::free_balance(SERP, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+	});
+}
+
+#[test]
+fn bid_with_loan_charge_mint_fee_on_win() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		set_mock_mint_fee_rate(Rate::saturating_from_rational(1, 10));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		set_mock_position(BOB, 150, 0);
+
+		// the mint fee is held in escrow along with the bid
+		assert_ok!(AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 100, 100));
+		assert_eq!(AuctionManagerModule::loan_funded_bid(0), Some((BOB, SERP, 100, 100, 10)));
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 100);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 990);
+		assert_eq!(Tokens::free_balance(SETUSD, &AuctionManagerModule::account_id()), 110);
+
+		// the mint fee is refunded when outbid
+		assert_ok!(AuctionModule::bid(Origin::signed(CAROL), 0, 150));
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+		assert_eq!(mock_position(BOB), (150, 0));
+
+		// the mint fee is charged when won
+		assert_ok!(AuctionManagerModule::bid_with_loan(Origin::signed(BOB), 0, 200, 150));
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 935);
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 200)));
+		assert_eq!(mock_position(BOB), (250, 150));
+		assert_eq!(Tokens::free_balance(SETUSD, &AuctionManagerModule::account_id()), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 215);
+	});
+}
+
+#[test]
+fn batch_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 300));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert_ok!(AuctionModule::bid(Origin::signed(CAROL), 1, 100));
+		assert_eq!(Tokens::free_balance(SETUSD, &CAROL), 900);
+		let bob_ref_count_0 = System::consumers(&BOB);
+		let carol_ref_count_0 = System::consumers(&CAROL);
+
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 20), (1, 200), (2, 5), (3, 100)]),
+			Error::<Runtime>::TooManyBids,
+		);
+
+		assert_ok!(AuctionManagerModule::batch_bid(
+			Origin::signed(BOB),
+			vec![(0, 20), (1, 200), (2, 5)]
+		));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::BatchBid {
+			bidder: BOB,
+			placed: vec![(0, 10), (1, 110)],
+			failed: vec![2],
+		}));
+		System::assert_has_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionBid {
+			auction_id: 1,
+			bidder: BOB,
+			bid_price: 110,
+		}));
+		assert_eq!(AuctionModule::auctions(0).unwrap().bid, Some((BOB, 10)));
+		assert_eq!(AuctionModule::auctions(0).unwrap().end, Some(101));
+		assert_eq!(AuctionModule::auctions(1).unwrap().bid, Some((BOB, 110)));
+		assert_eq!(AuctionModule::auctions(2).unwrap().bid, None);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 10);
+		assert_eq!(AuctionManagerModule::bid_escrow(1), 110);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 880);
+		assert_eq!(Tokens::free_balance(SETUSD, &CAROL), 1000);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 + 2);
+		assert_eq!(System::consumers(&CAROL), carol_ref_count_0 - 1);
+
+		// outbid the own bid and skip the auction not exists
+		assert_ok!(AuctionManagerModule::batch_bid(
+			Origin::signed(BOB),
+			vec![(0, 100), (9, 100)]
+		));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::BatchBid {
+			bidder: BOB,
+			placed: vec![(0, 20)],
+			failed: vec![9],
+		}));
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 870);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 + 2);
+	});
+}
+
+#[test]
+fn swap_bidders_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_ref_count_0 = System::consumers(&ALICE);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		AuctionManagerModule::swap_bidders(&BOB, None);
+
+		let bob_ref_count_1 = System::consumers(&BOB);
+		assert_eq!(bob_ref_count_1, bob_ref_count_0 + 1);
 
 		AuctionManagerModule::swap_bidders(&ALICE, Some(&BOB));
 
@@ -524,7 +1307,8 @@ fn cancel_collateral_auction_work() {
 		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 80));
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 920);
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 10);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 80);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 80);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 920);
 
@@ -541,8 +1325,9 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
 		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
 		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 10);
-		assert_eq!(CDPTreasuryModule::debit_pool(), 80);
-		assert_eq!(CDPTreasuryModule::surplus_pool(), 80);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
 		assert!(!AuctionManagerModule::collateral_auctions(0).is_some());
 		assert!(!AuctionModule::auction_info(0).is_some());
 
@@ -553,6 +1338,120 @@ fn cancel_collateral_auction_work() {
 	});
 }
 
+#[test]
+fn settle_reverse_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 110));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 100, 200));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 10, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 250));
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
+		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1020);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 90);
+		assert_noop!(
+			AuctionManagerModule::settle_reverse_auction(Origin::none(), 0),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+
+		let alice_ref_count_0 = System::consumers(&ALICE);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::cancel(Origin::none(), 0),
+			Error::<Runtime>::InReverseStage,
+		);
+		assert_noop!(
+			AuctionManagerModule::settle_reverse_auction(Origin::none(), 1),
+			Error::<Runtime>::NotInReverseStage,
+		);
+		assert_noop!(
+			AuctionManagerModule::settle_reverse_auction(Origin::none(), 2),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		// the price is required to settle
+		MockPriceSource::set_relative_price(None);
+		assert_noop!(
+			AuctionManagerModule::settle_reverse_auction(Origin::none(), 0),
+			Error::<Runtime>::InvalidFeedPrice,
+		);
+
+		// the last bidder takes the collateral worth the target at the locked price,
+		// the rest is refunded
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 4)));
+		assert_ok!(AuctionManagerModule::settle_reverse_auction(Origin::none(), 0));
+		System::assert_has_event(Event::AuctionManagerModule(crate::Event::CollateralAuctionDealt {
+			auction_id: 0,
+			collateral_type: SERP,
+			collateral_amount: 50,
+			winner: BOB,
+			payment_amount: 200,
+		}));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::ReverseAuctionSettled {
+			auction_id: 0,
+			winner: BOB,
+			collateral_amount: 50,
+			payment_amount: 200,
+			settle_price: Price::saturating_from_rational(1, 4),
+		}));
+		assert_eq!(Tokens::free_balance(SERP, &BOB), 1050);
+		assert_eq!(Tokens::free_balance(SERP, &ALICE), 1050);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 10);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 100);
+		assert!(AuctionManagerModule::collateral_auctions(0).is_none());
+		assert!(AuctionModule::auction_info(0).is_none());
+		assert_eq!(
+			AuctionManagerModule::settled_collateral_auction(0).map(|record| record.outcome),
+			Some(CollateralAuctionOutcome::Dealt)
+		);
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+
+		// the auction in forward stage can be cancelled then
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 1));
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(SERP), 0);
+	});
+}
+
+#[test]
+fn offchain_worker_settles_reverse_auction_in_shutdown() {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, SERP, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, SERP, 10, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 100));
+		mock_shutdown();
+
+		// offchain worker will settle the auction in reverse stage
+		run_to_block_offchain(2);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(
+			tx.call,
+			MockCall::AuctionManagerModule(crate::Call::settle_reverse_auction(0))
+		);
+		assert_ok!(AuctionManagerModule::settle_reverse_auction(Origin::none(), 0));
+
+		// auction is settled
+		assert!(AuctionManagerModule::collateral_auctions(0).is_none());
+		assert_eq!(Tokens::free_balance(SERP, &BOB), 1010);
+		assert!(pool_state.write().transactions.pop().is_none());
+	});
+}
+
 #[test]
 fn offchain_worker_cancels_auction_in_shutdown() {
 	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
@@ -664,3 +1563,161 @@ fn offchain_default_max_iterator_works() {
 		assert_eq!(pool_state.write().transactions.len(), 1001);
 	});
 }
+
+#[test]
+fn new_debit_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::new_debit_auction(DNAR, 0, 200),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::new_debit_auction(DNAR, 100, 0),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		assert_ok!(AuctionManagerModule::new_debit_auction(DNAR, 100, 200));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::NewDebitAuction {
+			auction_id: 0,
+			issuance_currency_id: DNAR,
+			initial_amount: 100,
+			fix_debit_amount: 200,
+		}));
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 200);
+		assert_eq!(AuctionManagerModule::total_issuance_in_debit_auction(DNAR), 100);
+		assert_eq!(AuctionManagerModule::get_total_debit_in_auction(), 200);
+		assert_eq!(AuctionManagerModule::get_total_issuance_in_auction(DNAR), 100);
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(orml_traits::AuctionInfo {
+				bid: None,
+				start: 1,
+				end: Some(2001)
+			})
+		);
+
+		let debit_auction = AuctionManagerModule::debit_auctions(0).unwrap();
+		assert_eq!(debit_auction.amount_for_sale(0, 200), 100);
+		assert_eq!(debit_auction.amount_for_sale(0, 400), 50);
+		assert_eq!(debit_auction.amount_for_sale(250, 500), 50);
+		assert_eq!(debit_auction.amount_for_sale(300, 200), 100);
+
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::new_debit_auction(DNAR, 100, 200),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+	});
+}
+
+#[test]
+fn debit_auction_bid_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 200), None),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(AuctionManagerModule::new_debit_auction(DNAR, 100, 200));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		assert_noop!(
+			AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 199), None),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert!(AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 200), None).is_ok());
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
+		assert_eq!(AuctionManagerModule::debit_auctions(0).unwrap().amount, 100);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 + 1);
+
+		assert_noop!(
+			AuctionManagerModule::debit_auction_bid_handler(2, 0, (CAROL, 205), Some((BOB, 200))),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert!(AuctionManagerModule::debit_auction_bid_handler(2, 0, (CAROL, 250), Some((BOB, 200))).is_ok());
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(SETUSD, &CAROL), 800);
+		assert_eq!(AuctionManagerModule::debit_auctions(0).unwrap().amount, 80);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0);
+	});
+}
+
+#[test]
+fn debit_auction_dealt_and_aborted() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(DNAR, 100, 200));
+		assert_ok!(AuctionManagerModule::new_debit_auction(DNAR, 50, 100));
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 300);
+		assert_eq!(AuctionManagerModule::total_issuance_in_debit_auction(DNAR), 150);
+
+		assert!(AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 250), None).is_ok());
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 250)));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DebitAuctionDealt {
+			auction_id: 0,
+			issuance_currency_id: DNAR,
+			issuance_amount: 80,
+			winner: BOB,
+			payment_amount: 200,
+		}));
+		assert_eq!(Tokens::free_balance(DNAR, &BOB), 1080);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+		assert!(AuctionManagerModule::debit_auctions(0).is_none());
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 100);
+		assert_eq!(AuctionManagerModule::total_issuance_in_debit_auction(DNAR), 50);
+
+		AuctionManagerModule::on_auction_ended(1, None);
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DebitAuctionAborted {
+			auction_id: 1,
+			issuance_currency_id: DNAR,
+			initial_amount: 50,
+			fix_debit_amount: 100,
+		}));
+		assert!(AuctionManagerModule::debit_auctions(1).is_none());
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::total_issuance_in_debit_auction(DNAR), 0);
+	});
+}
+
+#[test]
+fn cancel_debit_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(DNAR, 100, 200));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 200));
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 800);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 200);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		mock_shutdown();
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CancelAuction {
+			auction_id: 0,
+		}));
+
+		assert_eq!(Tokens::free_balance(SETUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(DNAR, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(AuctionManagerModule::bid_escrow(0), 0);
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::total_issuance_in_debit_auction(DNAR), 0);
+		assert!(AuctionManagerModule::debit_auctions(0).is_none());
+		assert!(AuctionModule::auction_info(0).is_none());
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+	});
+}
@@ -37,6 +37,11 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for auction_manager.
 pub trait WeightInfo {
 	fn cancel_collateral_auction() -> Weight;
+	fn settle_reverse_auction() -> Weight;
+	fn set_collateral_auction_params() -> Weight;
+	fn bid_with_loan() -> Weight;
+	fn batch_bid(c: u32) -> Weight;
+	fn on_initialize(c: u32) -> Weight;
 }
 
 /// Weights for auction_manager using the Setheum node and recommended hardware.
@@ -44,8 +49,35 @@ pub struct SetheumWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 	fn cancel_collateral_auction() -> Weight {
 		(78_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(9 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn settle_reverse_auction() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+	}
+	fn set_collateral_auction_params() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn bid_with_loan() -> Weight {
+		(176_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(18 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	fn batch_bid(c: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((98_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((12 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(c as Weight)))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 }
 
@@ -53,7 +85,34 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 impl WeightInfo for () {
 	fn cancel_collateral_auction() -> Weight {
 		(78_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn settle_reverse_auction() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
+	}
+	fn set_collateral_auction_params() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn bid_with_loan() -> Weight {
+		(176_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(18 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn batch_bid(c: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((98_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((12 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(c as Weight)))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 }
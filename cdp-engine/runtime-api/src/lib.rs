@@ -0,0 +1,36 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+//
+// This file is part of Ethical DeFi.
+//
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: BUSL-1.1 (Business Source License 1.1)
+
+//! Runtime API definition for the CDP engine module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use primitives::CurrencyId;
+use sp_std::prelude::*;
+use support::{Price, Rate, Ratio};
+
+pub use cdp_engine::LiquidationSimulation;
+
+sp_api::decl_runtime_apis! {
+	pub trait CDPEngineApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Simulate the liquidation of all CDPs under `currency_id` for hypothetical
+		/// prices and risk params, without mutating state.
+		fn simulate_liquidation(
+			currency_id: CurrencyId,
+			price_overrides: Vec<(CurrencyId, Price)>,
+			liquidation_ratio: Option<Ratio>,
+			liquidation_penalty: Option<Rate>,
+		) -> Option<LiquidationSimulation<AccountId>>;
+	}
+}
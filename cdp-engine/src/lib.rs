@@ -22,8 +22,11 @@ use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
 };
-use loans::Position;
-use orml_traits::{Change, MultiCurrency};
+use cdp_treasury::{
+	BadDebtManager, InsuranceFundManager, MultiStableCurrencyTreasury, OperationPauseManager, PausableOperation,
+};
+use loans::{DebitCurrencyProvider, MintFeeManager, Position};
+use orml_traits::{Change, Happened, MultiCurrency};
 use orml_utilities::OffchainErr;
 use primitives::{Amount, Balance, CurrencyId};
 use rand_chacha::{
@@ -36,7 +39,7 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{Bounded, One, Saturating, StaticLookup, Zero},
+	traits::{Bounded, CheckedDiv, One, Saturating, StaticLookup, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
@@ -60,6 +63,7 @@ pub const OFFCHAIN_WORKER_LOCK: &[u8] = b"setheum/cdp-engine/lock/";
 pub const OFFCHAIN_WORKER_MAX_ITERATIONS: &[u8] = b"setheum/cdp-engine/max-iterations/";
 pub const LOCK_DURATION: u64 = 100;
 pub const DEFAULT_MAX_ITERATIONS: u32 = 1000;
+pub const MAX_DEBIT_CAP_ALERT_THRESHOLDS: usize = 10;
 
 pub type LoansOf<T> = loans::Pallet<T>;
 
@@ -101,6 +105,19 @@ pub enum CDPStatus {
 	ChecksFailed(DispatchError),
 }
 
+/// The result of liquidation simulation for a collateral type
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct LiquidationSimulation<AccountId> {
+	/// The CDPs would be liquidated, as (owner, collateral amount, debit value)
+	pub unsafe_positions: Vec<(AccountId, Balance, Balance)>,
+	/// Total collateral amount would be seized by cdp treasury
+	pub total_collateral: Balance,
+	/// Total bad debt value would be deposited to cdp treasury as system debit
+	pub total_bad_debt_value: Balance,
+	/// Total liquidation penalty would be charged on the bad debt
+	pub total_penalty: Balance,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -142,7 +159,11 @@ pub mod module {
 		type MaxSwapSlippageCompareToOracle: Get<Ratio>;
 
 		/// The CDP treasury to maintain bad debts and surplus generated by CDPs
-		type CDPTreasury: CDPTreasuryExtended<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+		type CDPTreasury: CDPTreasuryExtended<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>
+			+ BadDebtManager
+			+ InsuranceFundManager
+			+ MultiStableCurrencyTreasury<Self::AccountId>
+			+ OperationPauseManager;
 
 		/// The price source of all types of currencies related to CDP
 		type PriceSource: PriceProvider<CurrencyId>;
@@ -154,6 +175,11 @@ pub mod module {
 		#[pallet::constant]
 		type UnsignedPriority: Get<TransactionPriority>;
 
+		/// The maximum number of CDPs that can be liquidated or settled in a
+		/// batch.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
@@ -205,6 +231,22 @@ pub mod module {
 		NotEnoughDebitDecrement,
 		/// convert debit value to debit balance failed
 		ConvertDebitBalanceFailed,
+		/// The amounts withdrawn by removing liquidity of lp collateral are below
+		/// the slippage limit
+		UnacceptableLiquidityWithdrawn,
+		/// The mint fee rate is invalid
+		InvalidMintFeeRate,
+		/// The batch is empty or exceeds the max batch size
+		InvalidBatchSize,
+		/// The debit value newly issued in current block exceeds the mint rate limit
+		ExceedMintRateLimit,
+		/// The debit cap alert thresholds must be ascending, at most 100% and
+		/// not exceed the max count
+		InvalidDebitCapAlertThresholds,
+		/// The stable currency is not supported by CDP treasury
+		InvalidStableCurrencyType,
+		/// The debit currency cannot be changed while the collateral type has debit
+		DebitCurrencyInUse,
 	}
 
 	#[pallet::event]
@@ -224,6 +266,18 @@ pub mod module {
 			collateral_type: CurrencyId,
 			owner: T::AccountId,
 		},
+		/// Failed to liquidate the CDP in a batch.
+		LiquidateCDPFailed {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			error: DispatchError,
+		},
+		/// Failed to settle the CDP in a batch.
+		SettleCDPFailed {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			error: DispatchError,
+		},
 		/// Directly close CDP has debit by handle debit with DEX.
 		CloseCDPInDebitByDEX {
 			collateral_type: CurrencyId,
@@ -252,6 +306,31 @@ pub mod module {
 			collateral_type: CurrencyId,
 			new_total_debit_value: Balance,
 		},
+		/// The one-off mint fee rate for specific collateral type updated.
+		MintFeeRateUpdated {
+			collateral_type: CurrencyId,
+			new_mint_fee_rate: Rate,
+		},
+		/// The total debit value of specific collateral type reached an alert
+		/// threshold of its hard cap.
+		DebitCapUtilisationAlert {
+			collateral_type: CurrencyId,
+			threshold: Ratio,
+			total_debit_value: Balance,
+			maximum_total_debit_value: Balance,
+		},
+		/// The alert thresholds of debit cap utilisation updated.
+		DebitCapAlertThresholdsUpdated { new_thresholds: Vec<Ratio> },
+		/// The mint rate limit per block for specific collateral type updated.
+		MintRateLimitUpdated {
+			collateral_type: CurrencyId,
+			new_mint_rate_limit: Option<Balance>,
+		},
+		/// The debit currency for specific collateral type updated.
+		DebitCurrencyUpdated {
+			collateral_type: CurrencyId,
+			new_debit_currency: Option<CurrencyId>,
+		},
 	}
 
 	/// Mapping from collateral type to its exchange rate of debit units and
@@ -269,6 +348,65 @@ pub mod module {
 	#[pallet::getter(fn collateral_params)]
 	pub type CollateralParams<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, RiskManagementParams, ValueQuery>;
 
+	/// Mapping from collateral type to the one-off fee rate charged on the
+	/// debit value newly issued under it
+	///
+	/// MintFeeRates: CurrencyId => Rate
+	#[pallet::storage]
+	#[pallet::getter(fn mint_fee_rate)]
+	pub type MintFeeRates<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultDebitCapAlertThresholds() -> Vec<Ratio> {
+		vec![
+			Ratio::saturating_from_rational(80, 100),
+			Ratio::saturating_from_rational(90, 100),
+			Ratio::one(),
+		]
+	}
+
+	/// The ascending utilisation thresholds of debit cap, an alert event is
+	/// emitted when the total debit value of a collateral type reaches them
+	///
+	/// DebitCapAlertThresholds: Vec<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_cap_alert_thresholds)]
+	pub type DebitCapAlertThresholds<T: Config> =
+		StorageValue<_, Vec<Ratio>, ValueQuery, DefaultDebitCapAlertThresholds>;
+
+	/// Mapping from collateral type to the highest alert threshold its debit
+	/// cap utilisation has reached
+	///
+	/// DebitCapAlertLevels: CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_cap_alert_level)]
+	pub type DebitCapAlertLevels<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// Mapping from collateral type to the max debit value that can be newly
+	/// issued under it per block
+	///
+	/// MintRateLimits: CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn mint_rate_limit)]
+	pub type MintRateLimits<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// Mapping from collateral type with mint rate limit to its total debit
+	/// balance at the start of the block, recorded on the first debit update
+	/// in the block
+	///
+	/// BlockStartTotalDebits: CurrencyId => Option<(BlockNumber, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn block_start_total_debit)]
+	pub type BlockStartTotalDebits<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (T::BlockNumber, Balance), OptionQuery>;
+
+	/// Mapping from collateral type to the stable currency issued as its debit,
+	/// the default stable currency is used if not set
+	///
+	/// DebitCurrencies: CurrencyId => Option<CurrencyId>
+	#[pallet::storage]
+	pub type DebitCurrencies<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, CurrencyId, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		#[allow(clippy::type_complexity)]
@@ -382,6 +520,107 @@ pub mod module {
 			Ok(())
 		}
 
+		/// Liquidate a batch of unsafe CDPs, each CDP is liquidated independently,
+		/// the failure of one CDP does not revert the others.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// - `cdps`: list of CDP's collateral type and owner, must not exceed `MaxBatchSize`.
+		#[pallet::weight(<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction())
+			.saturating_mul(cdps.len() as Weight))]
+		pub fn liquidate_batch(
+			origin: OriginFor<T>,
+			cdps: Vec<(CurrencyId, <T::Lookup as StaticLookup>::Source)>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			Self::ensure_valid_batch_size(cdps.len())?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+
+			let max_liquidation_weight =
+				<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction());
+			let mut consumed_weight: Weight = 0;
+			for (currency_id, who) in cdps {
+				let who = match T::Lookup::lookup(who) {
+					Ok(who) => who,
+					Err(_) => {
+						consumed_weight = consumed_weight.saturating_add(Self::batch_item_skipped_weight());
+						continue;
+					}
+				};
+				match Self::try_liquidate_unsafe_cdp(who.clone(), currency_id) {
+					Ok(weight) => consumed_weight = consumed_weight.saturating_add(weight),
+					Err(error) => {
+						// the CDP which is not unsafe or whose liquidation is paused is skipped by the
+						// checks before any change
+						let weight = if error == Error::<T>::MustBeUnsafe.into()
+							|| <T as Config>::CDPTreasury::ensure_operation_not_paused(
+								PausableOperation::Liquidate,
+								currency_id,
+							)
+							.is_err()
+						{
+							Self::batch_item_skipped_weight()
+						} else {
+							max_liquidation_weight
+						};
+						consumed_weight = consumed_weight.saturating_add(weight);
+						Self::deposit_event(Event::LiquidateCDPFailed {
+							collateral_type: currency_id,
+							owner: who,
+							error,
+						});
+					}
+				}
+			}
+			Ok(Some(consumed_weight).into())
+		}
+
+		/// Settle a batch of CDPs has debit after system shutdown, each CDP is
+		/// settled independently, the failure of one CDP does not revert the others.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// - `cdps`: list of CDP's collateral type and owner, must not exceed `MaxBatchSize`.
+		#[pallet::weight(<T as Config>::WeightInfo::settle().saturating_mul(cdps.len() as Weight))]
+		pub fn settle_batch(
+			origin: OriginFor<T>,
+			cdps: Vec<(CurrencyId, <T::Lookup as StaticLookup>::Source)>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			Self::ensure_valid_batch_size(cdps.len())?;
+			ensure!(T::EmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
+
+			let settle_weight = <T as Config>::WeightInfo::settle();
+			let mut consumed_weight: Weight = 0;
+			for (currency_id, who) in cdps {
+				let who = match T::Lookup::lookup(who) {
+					Ok(who) => who,
+					Err(_) => {
+						consumed_weight = consumed_weight.saturating_add(Self::batch_item_skipped_weight());
+						continue;
+					}
+				};
+				match Self::try_settle_cdp_has_debit(who.clone(), currency_id) {
+					Ok(_) => consumed_weight = consumed_weight.saturating_add(settle_weight),
+					Err(error) => {
+						// the CDP which has no debit is skipped before any change
+						let weight = if error == Error::<T>::NoDebitValue.into() {
+							Self::batch_item_skipped_weight()
+						} else {
+							settle_weight
+						};
+						consumed_weight = consumed_weight.saturating_add(weight);
+						Self::deposit_event(Event::SettleCDPFailed {
+							collateral_type: currency_id,
+							owner: who,
+							error,
+						});
+					}
+				}
+			}
+			Ok(Some(consumed_weight).into())
+		}
+
 		/// Update parameters related to risk management of CDP under specific
 		/// collateral type
 		///
@@ -443,6 +682,130 @@ pub mod module {
 			CollateralParams::<T>::insert(currency_id, collateral_params);
 			Ok(())
 		}
+
+		/// Update the one-off mint fee rate for specific collateral type. The fee
+		/// is charged when debit is issued and never accrues over time.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `mint_fee_rate`: the fee rate, must be less than 100%.
+		#[pallet::weight((<T as Config>::WeightInfo::set_mint_fee_rate(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_mint_fee_rate(origin: OriginFor<T>, currency_id: CurrencyId, mint_fee_rate: Rate) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			ensure!(mint_fee_rate < Rate::one(), Error::<T>::InvalidMintFeeRate);
+
+			MintFeeRates::<T>::insert(currency_id, mint_fee_rate);
+			Self::deposit_event(Event::MintFeeRateUpdated {
+				collateral_type: currency_id,
+				new_mint_fee_rate: mint_fee_rate,
+			});
+			Ok(())
+		}
+
+		/// Update the alert thresholds of debit cap utilisation for all collateral
+		/// types.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `thresholds`: ascending thresholds, each one must be at most 100%.
+		#[pallet::weight((<T as Config>::WeightInfo::set_debit_cap_alert_thresholds(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_debit_cap_alert_thresholds(origin: OriginFor<T>, thresholds: Vec<Ratio>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				thresholds.len() <= MAX_DEBIT_CAP_ALERT_THRESHOLDS
+					&& thresholds.windows(2).all(|pair| pair[0] < pair[1])
+					&& thresholds.iter().all(|threshold| *threshold <= Ratio::one()),
+				Error::<T>::InvalidDebitCapAlertThresholds
+			);
+
+			DebitCapAlertThresholds::<T>::put(thresholds.clone());
+			Self::deposit_event(Event::DebitCapAlertThresholdsUpdated {
+				new_thresholds: thresholds,
+			});
+			Ok(())
+		}
+
+		/// Update the max debit value that can be newly issued per block for
+		/// specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `mint_rate_limit`: the limit, `None` means no limit.
+		#[pallet::weight((<T as Config>::WeightInfo::set_mint_rate_limit(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_mint_rate_limit(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			mint_rate_limit: Option<Balance>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+
+			match mint_rate_limit {
+				Some(limit) => MintRateLimits::<T>::insert(currency_id, limit),
+				None => {
+					MintRateLimits::<T>::remove(currency_id);
+					BlockStartTotalDebits::<T>::remove(currency_id);
+				}
+			}
+			Self::deposit_event(Event::MintRateLimitUpdated {
+				collateral_type: currency_id,
+				new_mint_rate_limit: mint_rate_limit,
+			});
+			Ok(())
+		}
+
+		/// Update the stable currency issued as debit for specific collateral
+		/// type. It can only be changed when the collateral type has no debit.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `debit_currency_id`: the stable currency, `None` means the default
+		///   stable currency.
+		#[pallet::weight((<T as Config>::WeightInfo::set_debit_currency(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_debit_currency(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			debit_currency_id: Option<CurrencyId>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			if let Some(stable_currency_id) = debit_currency_id {
+				ensure!(
+					<T as Config>::CDPTreasury::get_stable_currency_ids().contains(&stable_currency_id),
+					Error::<T>::InvalidStableCurrencyType,
+				);
+			}
+			ensure!(
+				<LoansOf<T>>::total_positions(currency_id).debit.is_zero(),
+				Error::<T>::DebitCurrencyInUse,
+			);
+
+			DebitCurrencies::<T>::mutate_exists(currency_id, |maybe_debit_currency| {
+				*maybe_debit_currency = debit_currency_id.filter(|id| *id != T::GetSetUSDId::get());
+			});
+			Self::deposit_event(Event::DebitCurrencyUpdated {
+				collateral_type: currency_id,
+				new_debit_currency: debit_currency_id,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -458,6 +821,11 @@ pub mod module {
 						Self::check_cdp_status(*currency_id, collateral, debit),
 						CDPStatus::Unsafe
 					) || T::EmergencyShutdown::is_shutdown()
+						|| <T as Config>::CDPTreasury::ensure_operation_not_paused(
+							PausableOperation::Liquidate,
+							*currency_id,
+						)
+						.is_err()
 					{
 						return InvalidTransaction::Stale.into();
 					}
@@ -483,6 +851,56 @@ pub mod module {
 						.propagate(true)
 						.build()
 				}
+				Call::liquidate_batch(cdps) => {
+					if Self::ensure_valid_batch_size(cdps.len()).is_err() || T::EmergencyShutdown::is_shutdown() {
+						return InvalidTransaction::Stale.into();
+					}
+					// accept the batch if any CDP of it is still unsafe and its liquidation is not paused,
+					// the others are skipped when executed
+					let any_liquidatable = cdps.iter().any(|(currency_id, who)| {
+						T::Lookup::lookup(who.clone()).map_or(false, |account| {
+							let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &account);
+							matches!(
+								Self::check_cdp_status(*currency_id, collateral, debit),
+								CDPStatus::Unsafe
+							) && <T as Config>::CDPTreasury::ensure_operation_not_paused(
+								PausableOperation::Liquidate,
+								*currency_id,
+							)
+							.is_ok()
+						})
+					});
+					if !any_liquidatable {
+						return InvalidTransaction::Stale.into();
+					}
+
+					ValidTransaction::with_tag_prefix("CDPEngineOffchainWorker")
+						.priority(T::UnsignedPriority::get())
+						.and_provides((<frame_system::Pallet<T>>::block_number(), cdps))
+						.longevity(64_u64)
+						.propagate(true)
+						.build()
+				}
+				Call::settle_batch(cdps) => {
+					if Self::ensure_valid_batch_size(cdps.len()).is_err() || !T::EmergencyShutdown::is_shutdown() {
+						return InvalidTransaction::Stale.into();
+					}
+					// accept the batch if any CDP of it still has debit, the others are skipped when executed
+					let any_has_debit = cdps.iter().any(|(currency_id, who)| {
+						T::Lookup::lookup(who.clone())
+							.map_or(false, |account| !<LoansOf<T>>::positions(currency_id, account).debit.is_zero())
+					});
+					if !any_has_debit {
+						return InvalidTransaction::Stale.into();
+					}
+
+					ValidTransaction::with_tag_prefix("CDPEngineOffchainWorker")
+						.priority(T::UnsignedPriority::get())
+						.and_provides(cdps)
+						.longevity(64_u64)
+						.propagate(true)
+						.build()
+				}
 				_ => InvalidTransaction::Call.into(),
 			}
 		}
@@ -490,30 +908,48 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
-	fn submit_unsigned_liquidation_tx(currency_id: CurrencyId, who: T::AccountId) {
-		let who = T::Lookup::unlookup(who);
-		let call = Call::<T>::liquidate(currency_id, who.clone());
+	fn submit_unsigned_liquidation_batch_tx(currency_id: CurrencyId, accounts: Vec<T::AccountId>) {
+		let cdps = accounts
+			.into_iter()
+			.map(|who| (currency_id, T::Lookup::unlookup(who)))
+			.collect::<Vec<_>>();
+		let call = Call::<T>::liquidate_batch(cdps.clone());
 		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
 			log::info!(
 				target: "cdp-engine offchain worker",
-				"submit unsigned liquidation tx for \nCDP - AccountId {:?} CurrencyId {:?} \nfailed!",
-				who, currency_id,
+				"submit unsigned liquidation batch tx for \nCDPs {:?} \nfailed!",
+				cdps,
 			);
 		}
 	}
 
-	fn submit_unsigned_settlement_tx(currency_id: CurrencyId, who: T::AccountId) {
-		let who = T::Lookup::unlookup(who);
-		let call = Call::<T>::settle(currency_id, who.clone());
+	fn submit_unsigned_settlement_batch_tx(currency_id: CurrencyId, accounts: Vec<T::AccountId>) {
+		let cdps = accounts
+			.into_iter()
+			.map(|who| (currency_id, T::Lookup::unlookup(who)))
+			.collect::<Vec<_>>();
+		let call = Call::<T>::settle_batch(cdps.clone());
 		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
 			log::info!(
 				target: "cdp-engine offchain worker",
-				"submit unsigned settlement tx for \nCDP - AccountId {:?} CurrencyId {:?} \nfailed!",
-				who, currency_id,
+				"submit unsigned settlement batch tx for \nCDPs {:?} \nfailed!",
+				cdps,
 			);
 		}
 	}
 
+	/// Submit the found CDPs as a batch of liquidation or settlement tx.
+	fn submit_unsigned_batch_tx(currency_id: CurrencyId, accounts: Vec<T::AccountId>, is_shutdown: bool) {
+		if accounts.is_empty() {
+			return;
+		}
+		if is_shutdown {
+			Self::submit_unsigned_settlement_batch_tx(currency_id, accounts);
+		} else {
+			Self::submit_unsigned_liquidation_batch_tx(currency_id, accounts);
+		}
+	}
+
 	fn _offchain_worker() -> Result<(), OffchainErr> {
 		let collateral_currency_ids = T::CollateralCurrencyIds::get();
 		if collateral_currency_ids.len().is_zero() {
@@ -563,6 +999,8 @@ impl<T: Config> Pallet<T> {
 		let mut finished = true;
 		let mut iteration_count = 0;
 		let iteration_start_time = sp_io::offchain::timestamp();
+		let max_batch_size = T::MaxBatchSize::get().max(1) as usize;
+		let mut batch: Vec<T::AccountId> = Vec::new();
 
 		#[allow(clippy::while_let_on_iterator)]
 		while let Some((who, Position { collateral, debit })) = map_iterator.next() {
@@ -572,10 +1010,15 @@ impl<T: Config> Pallet<T> {
 					CDPStatus::Unsafe
 				) {
 				// liquidate unsafe CDPs before emergency shutdown occurs
-				Self::submit_unsigned_liquidation_tx(currency_id, who);
+				batch.push(who);
 			} else if is_shutdown && !debit.is_zero() {
 				// settle CDPs with debit after emergency shutdown occurs.
-				Self::submit_unsigned_settlement_tx(currency_id, who);
+				batch.push(who);
+			}
+
+			// submit the batch once it is full
+			if batch.len() == max_batch_size {
+				Self::submit_unsigned_batch_tx(currency_id, sp_std::mem::take(&mut batch), is_shutdown);
 			}
 
 			iteration_count += 1;
@@ -586,6 +1029,9 @@ impl<T: Config> Pallet<T> {
 			// extend offchain worker lock
 			guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
 		}
+		// submit the remaining CDPs
+		Self::submit_unsigned_batch_tx(currency_id, batch, is_shutdown);
+
 		let iteration_end_time = sp_io::offchain::timestamp();
 		log::debug!(
 			target: "cdp-engine offchain worker",
@@ -620,7 +1066,7 @@ impl<T: Config> Pallet<T> {
 	}
 
 	pub fn check_cdp_status(currency_id: CurrencyId, collateral_amount: Balance, debit_amount: Balance) -> CDPStatus {
-		let stable_currency_id = T::GetSetUSDId::get();
+		let stable_currency_id = Self::debit_currency_id(currency_id);
 		if let Some(feed_price) = T::PriceSource::get_relative_price(currency_id, stable_currency_id) {
 			let collateral_ratio =
 				Self::calculate_collateral_ratio(currency_id, collateral_amount, debit_amount, feed_price);
@@ -634,6 +1080,49 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Simulate the liquidation of all CDPs under `currency_id` for hypothetical
+	/// prices and risk params, without mutating state. The prices not in
+	/// `price_overrides` and the params which are `None` are taken from current
+	/// state. Return `None` if the price of collateral is unavailable.
+	pub fn simulate_liquidation(
+		currency_id: CurrencyId,
+		price_overrides: Vec<(CurrencyId, Price)>,
+		liquidation_ratio: Option<Ratio>,
+		liquidation_penalty: Option<Rate>,
+	) -> Option<LiquidationSimulation<T::AccountId>> {
+		let stable_currency_id = Self::debit_currency_id(currency_id);
+		let overridden_price = |id: CurrencyId| {
+			price_overrides
+				.iter()
+				.find(|(overridden_id, _)| *overridden_id == id)
+				.map(|(_, price)| *price)
+		};
+		let feed_price = match (overridden_price(currency_id), overridden_price(stable_currency_id)) {
+			(None, None) => T::PriceSource::get_relative_price(currency_id, stable_currency_id),
+			(base_price, quote_price) => {
+				let base_price = base_price.or_else(|| T::PriceSource::get_price(currency_id))?;
+				let quote_price = quote_price.or_else(|| T::PriceSource::get_price(stable_currency_id))?;
+				base_price.checked_div(&quote_price)
+			}
+		}?;
+		let liquidation_ratio = liquidation_ratio.unwrap_or_else(|| Self::get_liquidation_ratio(currency_id));
+		let liquidation_penalty = liquidation_penalty.unwrap_or_else(|| Self::get_liquidation_penalty(currency_id));
+
+		let mut simulation = LiquidationSimulation::default();
+		for (who, Position { collateral, debit }) in <loans::Positions<T>>::iter_prefix(currency_id) {
+			if Self::calculate_collateral_ratio(currency_id, collateral, debit, feed_price) < liquidation_ratio {
+				let bad_debt_value = Self::get_debit_value(currency_id, debit);
+				simulation.total_collateral = simulation.total_collateral.saturating_add(collateral);
+				simulation.total_bad_debt_value = simulation.total_bad_debt_value.saturating_add(bad_debt_value);
+				simulation.total_penalty = simulation
+					.total_penalty
+					.saturating_add(liquidation_penalty.saturating_mul_int(bad_debt_value));
+				simulation.unsafe_positions.push((who, collateral, bad_debt_value));
+			}
+		}
+		Some(simulation)
+	}
+
 	pub fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance {
 		Self::collateral_params(currency_id).maximum_total_debit_value
 	}
@@ -654,6 +1143,11 @@ impl<T: Config> Pallet<T> {
 			.unwrap_or_else(T::DefaultLiquidationPenalty::get)
 	}
 
+	/// Get the stable currency issued as debit for `currency_id`.
+	pub fn debit_currency_id(currency_id: CurrencyId) -> CurrencyId {
+		DebitCurrencies::<T>::get(currency_id).unwrap_or_else(T::GetSetUSDId::get)
+	}
+
 	pub fn get_debit_exchange_rate(currency_id: CurrencyId) -> ExchangeRate {
 		Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get)
 	}
@@ -711,9 +1205,17 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::InvalidCollateralType,
 		);
 		let loans_module_account = <LoansOf<T>>::account_id();
-
-		// issue stable coin in advance
-		<T as Config>::CDPTreasury::issue_debit(&loans_module_account, increase_debit_value, true)?;
+		let stable_currency_id = Self::debit_currency_id(currency_id);
+
+		// issue stable coin in advance, the mint fee is deducted from the issued stable coin
+		let mint_fee = <LoansOf<T>>::charge_mint_fee(who, currency_id, increase_debit_value)?;
+		let issued_stable_amount = increase_debit_value.saturating_sub(mint_fee);
+		<T as Config>::CDPTreasury::issue_debit_of(
+			stable_currency_id,
+			&loans_module_account,
+			issued_stable_amount,
+			true,
+		)?;
 
 		// get the actual increased collateral amount
 		let increase_collateral = match currency_id {
@@ -722,10 +1224,9 @@ impl<T: Config> Pallet<T> {
 				let token_1: CurrencyId = dex_share_1.into();
 
 				// NOTE: distribute half of the new issued stable coin to each components of lp token,
-				// need better distribution methods to avoid unused component tokens.
-				let stable_for_token_0 = increase_debit_value / 2;
-				let stable_for_token_1 = increase_debit_value.saturating_sub(stable_for_token_0);
-				let stable_currency_id = T::GetSetUSDId::get();
+				// the unused component tokens after adding liquidity are refunded to CDP owner.
+				let stable_for_token_0 = issued_stable_amount / 2;
+				let stable_for_token_1 = issued_stable_amount.saturating_sub(stable_for_token_0);
 				let stable_to_lp_component = |token: CurrencyId,
 				                              stable_amount: Balance|
 				 -> sp_std::result::Result<Balance, DispatchError> {
@@ -760,20 +1261,22 @@ impl<T: Config> Pallet<T> {
 				)?;
 
 				// refund unused lp component tokens
-				if let Some(remainer) = available_0.checked_sub(consumption_0) {
-					<T as Config>::Currency::transfer(token_0, &loans_module_account, who, remainer)?;
+				let remainer_0 = available_0.saturating_sub(consumption_0);
+				if !remainer_0.is_zero() {
+					<T as Config>::Currency::transfer(token_0, &loans_module_account, who, remainer_0)?;
 				}
-				if let Some(remainer) = available_1.checked_sub(consumption_1) {
-					<T as Config>::Currency::transfer(token_1, &loans_module_account, who, remainer)?;
+				let remainer_1 = available_1.saturating_sub(consumption_1);
+				if !remainer_1.is_zero() {
+					<T as Config>::Currency::transfer(token_1, &loans_module_account, who, remainer_1)?;
 				}
 
 				actual_increase_lp
 			}
 			_ => {
 				// swap stable coin to collateral
-				let limit = SwapLimit::ExactSupply(increase_debit_value, min_increase_collateral);
+				let limit = SwapLimit::ExactSupply(issued_stable_amount, min_increase_collateral);
 				let swap_path = T::DEX::get_best_price_swap_path(
-					T::GetSetUSDId::get(),
+					stable_currency_id,
 					currency_id,
 					limit,
 					T::AlternativeSwapPathJointList::get(),
@@ -820,7 +1323,7 @@ impl<T: Config> Pallet<T> {
 		);
 
 		let loans_module_account = <LoansOf<T>>::account_id();
-		let stable_currency_id = T::GetSetUSDId::get();
+		let stable_currency_id = Self::debit_currency_id(currency_id);
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
 		// ensure collateral of CDP is enough
@@ -831,14 +1334,16 @@ impl<T: Config> Pallet<T> {
 				let token_0: CurrencyId = dex_share_0.into();
 				let token_1: CurrencyId = dex_share_1.into();
 
-				// remove liquidity to get component tokens of lp token
+				// remove liquidity to get component tokens of lp token in limit of slippage
+				let (min_withdrawn_0, min_withdrawn_1) =
+					Self::get_lp_components_withdrawn_limit(currency_id, decrease_collateral)?;
 				let (available_0, available_1) = T::DEX::remove_liquidity(
 					&loans_module_account,
 					token_0,
 					token_1,
 					decrease_collateral,
-					Zero::zero(),
-					Zero::zero()
+					min_withdrawn_0,
+					min_withdrawn_1
 				)?;
 				let component_to_stable = |token: CurrencyId,
 				                           amount: Balance|
@@ -900,7 +1405,7 @@ impl<T: Config> Pallet<T> {
 		<LoansOf<T>>::update_loan(who, currency_id, collateral_adjustment, debit_adjustment)?;
 
 		// repay the debit of CDP
-		<T as Config>::CDPTreasury::burn_debit(&loans_module_account, decrease_debit_value)?;
+		<T as Config>::CDPTreasury::burn_debit_of(stable_currency_id, &loans_module_account, decrease_debit_value)?;
 
 		// refund extra stable coin to the CDP owner
 		<T as Config>::Currency::transfer(stable_currency_id, &loans_module_account, who, refund_stable)?;
@@ -922,7 +1427,7 @@ impl<T: Config> Pallet<T> {
 
 		// confiscate collateral in cdp to cdp treasury
 		// and decrease CDP's debit to zero
-		let settle_price: Price = T::PriceSource::get_relative_price(T::GetSetUSDId::get(), currency_id)
+		let settle_price: Price = T::PriceSource::get_relative_price(Self::debit_currency_id(currency_id), currency_id)
 			.ok_or(Error::<T>::InvalidFeedPrice)?;
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let confiscate_collateral_amount =
@@ -959,11 +1464,15 @@ impl<T: Config> Pallet<T> {
 		let debit_value = Self::get_debit_value(currency_id, debit);
 		let collateral_supply = collateral.min(max_collateral_amount);
 
-		let (actual_supply_collateral, _) = <T as Config>::CDPTreasury::swap_collateral_to_stable(
-			currency_id,
-			SwapLimit::ExactTarget(collateral_supply, debit_value),
-			false,
-		)?;
+		let debit_currency_id = Self::debit_currency_id(currency_id);
+		let (actual_supply_collateral, actual_target_amount) =
+			<T as Config>::CDPTreasury::swap_collateral_to_stable_of(
+				debit_currency_id,
+				currency_id,
+				SwapLimit::ExactTarget(collateral_supply, debit_value),
+				false,
+			)?;
+		<T as Config>::CDPTreasury::on_collateral_recovered(currency_id, debit_currency_id, actual_target_amount);
 
 		// refund remain collateral to CDP owner
 		let refund_collateral_amount = collateral
@@ -981,8 +1490,84 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Ensure the debit value newly issued under `currency_id` in current block
+	/// does not exceed the mint rate limit.
+	fn check_mint_rate_limit(currency_id: CurrencyId, total_debit_balance: Balance) -> DispatchResult {
+		if let Some(limit) = Self::mint_rate_limit(currency_id) {
+			// the total debit at the start of block is recorded on the first debit
+			// update in the block
+			if let Some((block_number, start_total_debit)) = Self::block_start_total_debit(currency_id) {
+				if block_number == <frame_system::Pallet<T>>::block_number() {
+					let issued_debit_value =
+						Self::get_debit_value(currency_id, total_debit_balance.saturating_sub(start_total_debit));
+					ensure!(issued_debit_value <= limit, Error::<T>::ExceedMintRateLimit);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Emit alert event when the debit cap utilisation of `currency_id` reaches
+	/// a higher threshold than before, and record the reached threshold.
+	fn update_debit_cap_alert_level(currency_id: CurrencyId, total_debit_value: Balance, hard_cap: Balance) {
+		let utilisation = Ratio::checked_from_rational(total_debit_value, hard_cap).unwrap_or_else(|| {
+			if total_debit_value.is_zero() {
+				Ratio::zero()
+			} else {
+				Ratio::max_value()
+			}
+		});
+		let reached_threshold = Self::debit_cap_alert_thresholds()
+			.into_iter()
+			.filter(|threshold| utilisation >= *threshold)
+			.last();
+		let previous_threshold = Self::debit_cap_alert_level(currency_id);
+
+		if reached_threshold != previous_threshold {
+			if let Some(threshold) = reached_threshold {
+				if previous_threshold.map_or(true, |previous| threshold > previous) {
+					Self::deposit_event(Event::DebitCapUtilisationAlert {
+						collateral_type: currency_id,
+						threshold,
+						total_debit_value,
+						maximum_total_debit_value: hard_cap,
+					});
+				}
+			}
+			DebitCapAlertLevels::<T>::mutate_exists(currency_id, |level| *level = reached_threshold);
+		}
+	}
+
+	/// Ensure the size of CDPs batch is valid.
+	fn ensure_valid_batch_size(len: usize) -> DispatchResult {
+		ensure!(
+			len > 0 && len <= T::MaxBatchSize::get() as usize,
+			Error::<T>::InvalidBatchSize
+		);
+		Ok(())
+	}
+
+	/// The weight of a CDP in batch which is skipped by the check before any
+	/// change.
+	fn batch_item_skipped_weight() -> Weight {
+		<T as frame_system::Config>::DbWeight::get().reads(4)
+	}
+
+	/// Liquidate unsafe cdp, all changes are reverted if it failed.
+	#[transactional]
+	fn try_liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Weight, DispatchError> {
+		Self::liquidate_unsafe_cdp(who, currency_id)
+	}
+
+	/// Settle cdp has debit, all changes are reverted if it failed.
+	#[transactional]
+	fn try_settle_cdp_has_debit(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		Self::settle_cdp_has_debit(who, currency_id)
+	}
+
 	// liquidate unsafe cdp
 	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Weight, DispatchError> {
+		<T as Config>::CDPTreasury::ensure_operation_not_paused(PausableOperation::Liquidate, currency_id)?;
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
 		// ensure the cdp is unsafe
@@ -999,48 +1584,101 @@ impl<T: Config> Pallet<T> {
 
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let target_stable_amount = Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value);
+		let stable_currency_id = Self::debit_currency_id(currency_id);
 
 		match currency_id {
 			CurrencyId::DexShare(dex_share_0, dex_share_1) => {
 				let token_0: CurrencyId = dex_share_0.into();
 				let token_1: CurrencyId = dex_share_1.into();
 
-				// remove liqudity first
-				let (amount_0, amount_1) =
-					<T as Config>::CDPTreasury::remove_liquidity_for_lp_collateral(currency_id, collateral)?;
-
-				// if these's stable
-				let stable_currency_id = T::GetSetUSDId::get();
-				if token_0 == stable_currency_id || token_1 == stable_currency_id {
-					let (existing_stable, need_handle_currency, handle_amount) = if token_0 == stable_currency_id {
-						(amount_0, token_1, amount_1)
+				// remove liqudity first, the withdrawn amounts must be in limit of slippage
+				if let Ok((amount_0, amount_1)) = Self::try_remove_lp_collateral(currency_id, collateral) {
+					// if these's stable
+					if token_0 == stable_currency_id || token_1 == stable_currency_id {
+						let (existing_stable, need_handle_currency, handle_amount) = if token_0 == stable_currency_id {
+							(amount_0, token_1, amount_1)
+						} else {
+							(amount_1, token_0, amount_0)
+						};
+
+						// these's stable refund
+						if existing_stable > target_stable_amount {
+							<T as Config>::CDPTreasury::withdraw_collateral(
+								&who,
+								stable_currency_id,
+								existing_stable
+									.checked_sub(target_stable_amount)
+									.expect("ensured existing stable amount greater than target; qed"),
+							)?;
+						}
+
+						let remain_target = target_stable_amount.saturating_sub(existing_stable);
+						let remain_bad_debt = Self::bad_debt_share(bad_debt_value, remain_target, target_stable_amount);
+
+						// the existing stable taken realises its part of the liquidation penalty
+						<T as Config>::CDPTreasury::on_liquidation_penalty(
+							stable_currency_id,
+							existing_stable
+								.min(target_stable_amount)
+								.saturating_sub(bad_debt_value.saturating_sub(remain_bad_debt)),
+						);
+						Self::handle_liquidated_collateral(
+							&who,
+							stable_currency_id,
+							need_handle_currency,
+							handle_amount,
+							remain_target,
+							remain_bad_debt,
+						)?;
 					} else {
-						(amount_1, token_0, amount_0)
-					};
-
-					// these's stable refund
-					if existing_stable > target_stable_amount {
-						<T as Config>::CDPTreasury::withdraw_collateral(
+						// token_0 and token_1 take target_stable in proportion to their value
+						let (target_0, target_1) = Self::split_target_by_value(
+							stable_currency_id,
+							token_0,
+							amount_0,
+							token_1,
+							amount_1,
+							target_stable_amount,
+						)?;
+						let bad_debt_0 = Self::bad_debt_share(bad_debt_value, target_0, target_stable_amount);
+						Self::handle_liquidated_collateral(
 							&who,
 							stable_currency_id,
-							existing_stable
-								.checked_sub(target_stable_amount)
-								.expect("ensured existing stable amount greater than target; qed"),
+							token_0,
+							amount_0,
+							target_0,
+							bad_debt_0,
+						)?;
+						Self::handle_liquidated_collateral(
+							&who,
+							stable_currency_id,
+							token_1,
+							amount_1,
+							target_1,
+							bad_debt_value.saturating_sub(bad_debt_0),
 						)?;
 					}
-
-					let remain_target = target_stable_amount.saturating_sub(existing_stable);
-					Self::handle_liquidated_collateral(&who, need_handle_currency, handle_amount, remain_target)?;
 				} else {
-					// token_0 and token_1 each take half target_stable
-					let target_0 = target_stable_amount / 2;
-					let target_1 = target_stable_amount.saturating_sub(target_0);
-					Self::handle_liquidated_collateral(&who, token_0, amount_0, target_0)?;
-					Self::handle_liquidated_collateral(&who, token_1, amount_1, target_1)?;
+					// the pool is skewed away from the price of oracle, handle the lp collateral as it is
+					Self::handle_liquidated_collateral(
+						&who,
+						stable_currency_id,
+						currency_id,
+						collateral,
+						target_stable_amount,
+						bad_debt_value,
+					)?;
 				}
 			}
 			_ => {
-				Self::handle_liquidated_collateral(&who, currency_id, collateral, target_stable_amount)?;
+				Self::handle_liquidated_collateral(
+					&who,
+					stable_currency_id,
+					currency_id,
+					collateral,
+					target_stable_amount,
+					bad_debt_value,
+				)?;
 			}
 		}
 
@@ -1053,12 +1691,97 @@ impl<T: Config> Pallet<T> {
 		});
 		Ok(T::WeightInfo::liquidate_by_dex())
 	}
-	
+
+	/// Remove the liquidity of liquidated lp collateral, all changes are
+	/// reverted if the withdrawn amounts are out of the limit of slippage.
+	#[transactional]
+	fn try_remove_lp_collateral(
+		lp_currency_id: CurrencyId,
+		lp_amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let (min_withdrawn_0, min_withdrawn_1) = Self::get_lp_components_withdrawn_limit(lp_currency_id, lp_amount)?;
+		let (amount_0, amount_1) =
+			<T as Config>::CDPTreasury::remove_liquidity_for_lp_collateral(lp_currency_id, lp_amount)?;
+		ensure!(
+			amount_0 >= min_withdrawn_0 && amount_1 >= min_withdrawn_1,
+			Error::<T>::UnacceptableLiquidityWithdrawn
+		);
+		Ok((amount_0, amount_1))
+	}
+
+	/// Get the minimal amounts of lp components that must be withdrawn by removing
+	/// `lp_amount` of `lp_currency_id`. Each component is expected to take half value
+	/// of the lp token at the price of oracle, limited by the max swap slippage.
+	pub fn get_lp_components_withdrawn_limit(
+		lp_currency_id: CurrencyId,
+		lp_amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let (token_0, token_1) = lp_currency_id
+			.split_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCollateralType)?;
+		let stable_currency_id = Self::debit_currency_id(lp_currency_id);
+		let half_lp_value = T::PriceSource::get_relative_price(lp_currency_id, stable_currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?
+			.saturating_mul_int(lp_amount)
+			/ 2;
+		let slippage_limit = Ratio::one().saturating_sub(T::MaxSwapSlippageCompareToOracle::get());
+		let min_withdrawn = |token: CurrencyId| -> sp_std::result::Result<Balance, DispatchError> {
+			let price = T::PriceSource::get_relative_price(stable_currency_id, token)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			Ok(slippage_limit.saturating_mul_int(price.saturating_mul_int(half_lp_value)))
+		};
+
+		Ok((min_withdrawn(token_0)?, min_withdrawn(token_1)?))
+	}
+
+	/// Split `target_stable_amount` of `stable_currency_id` between two lp
+	/// components in proportion to the value of `amount_0` and `amount_1` at the
+	/// price of oracle.
+	pub fn split_target_by_value(
+		stable_currency_id: CurrencyId,
+		token_0: CurrencyId,
+		amount_0: Balance,
+		token_1: CurrencyId,
+		amount_1: Balance,
+		target_stable_amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let value_0 = T::PriceSource::get_relative_price(token_0, stable_currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?
+			.saturating_mul_int(amount_0);
+		let value_1 = T::PriceSource::get_relative_price(token_1, stable_currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?
+			.saturating_mul_int(amount_1);
+
+		// each component takes half target if both of them have no value
+		let target_0 = Ratio::checked_from_rational(value_0, value_0.saturating_add(value_1))
+			.map(|proportion| proportion.saturating_mul_int(target_stable_amount))
+			.unwrap_or(target_stable_amount / 2);
+		Ok((target_0, target_stable_amount.saturating_sub(target_0)))
+	}
+
+	/// Get the share of `bad_debt_value` covered by `target` out of the total
+	/// `target_stable_amount` of a liquidation.
+	fn bad_debt_share(bad_debt_value: Balance, target: Balance, target_stable_amount: Balance) -> Balance {
+		Ratio::checked_from_rational(target, target_stable_amount)
+			.map(|proportion| proportion.saturating_mul_int(bad_debt_value))
+			.unwrap_or_default()
+	}
+
+	/// Swap `amount` of liquidated `currency_id` to `target_stable_amount` of
+	/// `stable_currency_id` by DEX, or create collateral auctions if the swap
+	/// fails. Collateral auctions are settled in the default stable currency, so
+	/// for other stable currencies the target is converted at the price of
+	/// oracle and the `bad_debt_value` covered by the collateral is moved into
+	/// the debit pool of the default stable currency. The liquidation penalty
+	/// above `bad_debt_value` contributes to the insurance fund once realised
+	/// by the swap or the auction proceeds.
 	pub fn handle_liquidated_collateral(
 		who: &T::AccountId,
+		stable_currency_id: CurrencyId,
 		currency_id: CurrencyId,
 		amount: Balance,
 		target_stable_amount: Balance,
+		bad_debt_value: Balance,
 	) -> DispatchResult {
 		if target_stable_amount.is_zero() {
 			// refund collateral to CDP owner
@@ -1074,18 +1797,30 @@ impl<T: Config> Pallet<T> {
 			.reciprocal()
 			.unwrap_or_else(Ratio::max_value)
 			.saturating_mul_int(
-				T::PriceSource::get_relative_price(T::GetSetUSDId::get(), currency_id)
+				T::PriceSource::get_relative_price(stable_currency_id, currency_id)
 					.expect("the oracle price should be avalible because liquidation are triggered by it.")
 					.saturating_mul_int(target_stable_amount),
 			);
 		let collateral_supply = amount.min(max_supply_limit);
 
-		// try swap collateral to stable to settle debit swap succeed.
-		if let Ok((actual_supply_collateral, _)) = <T as Config>::CDPTreasury::swap_collateral_to_stable(
-			currency_id,
-			SwapLimit::ExactTarget(collateral_supply, target_stable_amount),
-			false,
-		) {
+		// try swap collateral to stable to settle debit swap succeed, unless liquidation by DEX
+		// is paused for the collateral.
+		let swap_result =
+			<T as Config>::CDPTreasury::ensure_operation_not_paused(PausableOperation::LiquidateByDex, currency_id)
+				.and_then(|_| {
+					<T as Config>::CDPTreasury::swap_collateral_to_stable_of(
+						stable_currency_id,
+						currency_id,
+						SwapLimit::ExactTarget(collateral_supply, target_stable_amount),
+						false,
+					)
+				});
+		if let Ok((actual_supply_collateral, actual_target_amount)) = swap_result {
+			<T as Config>::CDPTreasury::on_collateral_recovered(currency_id, stable_currency_id, actual_target_amount);
+			<T as Config>::CDPTreasury::on_liquidation_penalty(
+				stable_currency_id,
+				actual_target_amount.saturating_sub(bad_debt_value),
+			);
 			let refund_collateral_amount = amount
 				.checked_sub(actual_supply_collateral)
 				.expect("swap succecced means collateral >= actual_supply_collateral; qed");
@@ -1096,10 +1831,32 @@ impl<T: Config> Pallet<T> {
 			}
 		} else {
 			// if cannot liquidate by swap, create collateral auctions by cdp treasury
+			let default_stable_currency_id = T::GetSetUSDId::get();
+			let (auction_target, auction_bad_debt) = if stable_currency_id == default_stable_currency_id {
+				(target_stable_amount, bad_debt_value)
+			} else {
+				let price = T::PriceSource::get_relative_price(stable_currency_id, default_stable_currency_id)
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
+				<T as Config>::CDPTreasury::move_debit_of(
+					stable_currency_id,
+					bad_debt_value,
+					default_stable_currency_id,
+					price.saturating_mul_int(bad_debt_value),
+				)?;
+				(
+					price.saturating_mul_int(target_stable_amount),
+					price.saturating_mul_int(bad_debt_value),
+				)
+			};
+			<T as Config>::CDPTreasury::on_liquidation_penalty_auctioned(
+				currency_id,
+				auction_target.saturating_sub(auction_bad_debt),
+				auction_target,
+			);
 			<T as Config>::CDPTreasury::create_collateral_auctions(
 				currency_id,
 				amount,
-				target_stable_amount,
+				auction_target,
 				who.clone(),
 				true,
 			)?;
@@ -1122,7 +1879,7 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 	) -> DispatchResult {
 		if !debit_balance.is_zero() {
 			let debit_value = Self::get_debit_value(currency_id, debit_balance);
-			let feed_price = <T as Config>::PriceSource::get_relative_price(currency_id, T::GetSetUSDId::get())
+			let feed_price = <T as Config>::PriceSource::get_relative_price(currency_id, Self::debit_currency_id(currency_id))
 				.ok_or(Error::<T>::InvalidFeedPrice)?;
 			let collateral_ratio =
 				Self::calculate_collateral_ratio(currency_id, collateral_balance, debit_balance, feed_price);
@@ -1158,11 +1915,41 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 		let total_debit_value = Self::get_debit_value(currency_id, total_debit_balance);
 
 		ensure!(total_debit_value <= hard_cap, Error::<T>::ExceedDebitValueHardCap,);
+		Self::check_mint_rate_limit(currency_id, total_debit_balance)?;
 
 		Ok(())
 	}
 }
 
+impl<T: Config> Happened<(CurrencyId, Balance, Balance)> for Pallet<T> {
+	fn happened(&(currency_id, previous_total_debit, total_debit): &(CurrencyId, Balance, Balance)) {
+		if Self::mint_rate_limit(currency_id).is_some() {
+			let now = <frame_system::Pallet<T>>::block_number();
+			if Self::block_start_total_debit(currency_id).map_or(true, |(block_number, _)| block_number != now) {
+				BlockStartTotalDebits::<T>::insert(currency_id, (now, previous_total_debit));
+			}
+		}
+
+		Self::update_debit_cap_alert_level(
+			currency_id,
+			Self::get_debit_value(currency_id, total_debit),
+			Self::maximum_total_debit_value(currency_id),
+		);
+	}
+}
+
+impl<T: Config> DebitCurrencyProvider for Pallet<T> {
+	fn get_debit_currency_id(currency_id: CurrencyId) -> CurrencyId {
+		Self::debit_currency_id(currency_id)
+	}
+}
+
+impl<T: Config> MintFeeManager for Pallet<T> {
+	fn get_mint_fee_rate(currency_id: CurrencyId) -> Rate {
+		Self::mint_fee_rate(currency_id)
+	}
+}
+
 /// Pick a new PRN, in the range [0, `max`) (exclusive).
 fn pick_u32<R: RngCore>(rng: &mut R, max: u32) -> u32 {
 	rng.next_u32() % max
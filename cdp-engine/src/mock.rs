@@ -129,7 +129,10 @@ impl loans::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type RiskManager = CDPEngineModule;
+	type MintFeeManager = CDPEngineModule;
+	type DebitCurrencyProvider = CDPEngineModule;
 	type CDPTreasury = CDPTreasuryModule;
+	type OnUpdateLoan = CDPEngineModule;
 	type PalletId = LoansPalletId;
 }
 
@@ -158,6 +161,7 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 			SERP => SERP_PRICE.with(|v| *v.borrow()),
 			DNAR => DNAR_PRICE.with(|v| *v.borrow()),
 			SETUSD => Some(Price::one()),
+			SETR => Some(Price::one()),
 			LP_SETUSD_DNAR => LP_SETUSD_DNAR_PRICE.with(|v| *v.borrow()),
 			LP_DNAR_SERP => LP_DNAR_SERP_PRICE.with(|v| *v.borrow()),
 			_ => None,
@@ -361,6 +365,7 @@ impl SerpTreasury<AccountId> for MockSerpTreasury {
 parameter_types! {
 	pub const GetSetUSDId: CurrencyId = SETUSD;
 	pub const MaxAuctionsCount: u32 = 10_000;
+	pub const BalanceSheetSnapshotCapacity: u32 = 10;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![SERP],
@@ -370,13 +375,19 @@ impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type GetSetUSDId = GetSetUSDId;
+	type StableCurrencyIds = StableCurrencyIds;
 	type AuctionManagerHandler = MockAuctionManager;
+	type DebitAuctionManagerHandler = ();
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type SerpTreasury = MockSerpTreasury;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
+	type PriceSource = MockPriceSource;
+	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type BalanceSheetSnapshotCapacity = BalanceSheetSnapshotCapacity;
 	type WeightInfo = ();
 }
 
@@ -447,7 +458,8 @@ parameter_types! {
 	pub const MinimumDebitValue: Balance = 2;
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::saturating_from_rational(50, 100);
 	pub const UnsignedPriority: u64 = 1 << 20;
-	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![SERP, DNAR];
+	pub const MaxBatchSize: u32 = 50;
+	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![SERP, DNAR, LP_SETUSD_DNAR];
 	pub DefaultSwapParitalPathList: Vec<Vec<CurrencyId>> = vec![
 		vec![SETUSD],
 		vec![SETM, SETUSD],
@@ -467,6 +479,7 @@ impl Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
 	type UnsignedPriority = UnsignedPriority;
+	type MaxBatchSize = MaxBatchSize;
 	type EmergencyShutdown = MockEmergencyShutdown;
 	type Currency = Currencies;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
@@ -12,7 +12,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Call as MockCall, Event, *};
-use orml_traits::MultiCurrency;
+use orml_traits::{Happened, MultiCurrency};
 use sp_core::offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
 use sp_io::offchain;
 use sp_runtime::{
@@ -28,7 +28,6 @@ fn run_to_block_offchain(n: u64) {
 	while System::block_number() < n {
 		System::set_block_number(System::block_number() + 1);
 		Timestamp::set_timestamp((System::block_number() as u64 * BLOCK_TIME) + INIT_TIMESTAMP);
-		CDPEngineModule::on_initialize(System::block_number());
 		CDPEngineModule::offchain_worker(System::block_number());
 		// this unlocks the concurrency storage lock so offchain_worker will fire next block
 		offchain::sleep_until(offchain::timestamp().add(Duration::from_millis(LOCK_DURATION + 200)));
@@ -66,6 +65,74 @@ fn check_cdp_status_work() {
 	});
 }
 
+#[test]
+fn simulate_liquidation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 500));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, SERP, 100, 100));
+
+		assert_eq!(
+			CDPEngineModule::simulate_liquidation(SERP, vec![], None, None),
+			Some(LiquidationSimulation::default())
+		);
+
+		// hypothetical price
+		assert_eq!(
+			CDPEngineModule::simulate_liquidation(SERP, vec![(SERP, Price::saturating_from_rational(7, 10))], None, None),
+			Some(LiquidationSimulation {
+				unsafe_positions: vec![(ALICE, 100, 50)],
+				total_collateral: 100,
+				total_bad_debt_value: 50,
+				total_penalty: 10,
+			})
+		);
+
+		// hypothetical params
+		assert_eq!(
+			CDPEngineModule::simulate_liquidation(
+				SERP,
+				vec![],
+				Some(Ratio::saturating_from_rational(3, 1)),
+				Some(Rate::saturating_from_rational(1, 10))
+			),
+			Some(LiquidationSimulation {
+				unsafe_positions: vec![(ALICE, 100, 50)],
+				total_collateral: 100,
+				total_bad_debt_value: 50,
+				total_penalty: 5,
+			})
+		);
+
+		let simulation = CDPEngineModule::simulate_liquidation(
+			SERP,
+			vec![(SERP, Price::saturating_from_rational(1, 10))],
+			None,
+			None,
+		)
+		.unwrap();
+		assert_eq!(simulation.unsafe_positions.len(), 2);
+		assert_eq!(simulation.total_collateral, 200);
+		assert_eq!(simulation.total_bad_debt_value, 60);
+		assert_eq!(simulation.total_penalty, 12);
+
+		// state is not changed
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 500);
+		assert_eq!(LoansModule::positions(SERP, BOB).debit, 100);
+		assert_eq!(CDPEngineModule::get_liquidation_ratio(SERP), Ratio::saturating_from_rational(3, 2));
+
+		MockPriceSource::set_price(SERP, None);
+		assert_eq!(CDPEngineModule::simulate_liquidation(SERP, vec![], None, None), None);
+	});
+}
+
 #[test]
 fn get_debit_exchange_rate_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -241,6 +308,164 @@ fn check_debit_cap_work() {
 	});
 }
 
+#[test]
+fn set_debit_cap_alert_thresholds_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(
+			CDPEngineModule::debit_cap_alert_thresholds(),
+			vec![
+				Ratio::saturating_from_rational(80, 100),
+				Ratio::saturating_from_rational(90, 100),
+				Ratio::one()
+			]
+		);
+		assert_noop!(
+			CDPEngineModule::set_debit_cap_alert_thresholds(Origin::signed(5), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_debit_cap_alert_thresholds(
+				Origin::signed(1),
+				vec![Ratio::saturating_from_rational(90, 100), Ratio::saturating_from_rational(80, 100)]
+			),
+			Error::<Runtime>::InvalidDebitCapAlertThresholds
+		);
+		assert_noop!(
+			CDPEngineModule::set_debit_cap_alert_thresholds(
+				Origin::signed(1),
+				vec![Ratio::saturating_from_rational(110, 100)]
+			),
+			Error::<Runtime>::InvalidDebitCapAlertThresholds
+		);
+
+		assert_ok!(CDPEngineModule::set_debit_cap_alert_thresholds(
+			Origin::signed(1),
+			vec![Ratio::saturating_from_rational(50, 100), Ratio::one()]
+		));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::DebitCapAlertThresholdsUpdated {
+			new_thresholds: vec![Ratio::saturating_from_rational(50, 100), Ratio::one()],
+		}));
+		assert_eq!(
+			CDPEngineModule::debit_cap_alert_thresholds(),
+			vec![Ratio::saturating_from_rational(50, 100), Ratio::one()]
+		);
+	});
+}
+
+#[test]
+fn debit_cap_utilisation_alert_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		System::reset_events();
+
+		// check debit cap has no side effects
+		assert_ok!(CDPEngineModule::check_debit_cap(SERP, 80000));
+		assert_eq!(CDPEngineModule::debit_cap_alert_level(SERP), None);
+		assert!(System::events().is_empty());
+
+		CDPEngineModule::happened(&(SERP, 0, 70000));
+		assert_eq!(CDPEngineModule::debit_cap_alert_level(SERP), None);
+		assert!(System::events().is_empty());
+
+		CDPEngineModule::happened(&(SERP, 0, 80000));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::DebitCapUtilisationAlert {
+			collateral_type: SERP,
+			threshold: Ratio::saturating_from_rational(80, 100),
+			total_debit_value: 8000,
+			maximum_total_debit_value: 10000,
+		}));
+		assert_eq!(
+			CDPEngineModule::debit_cap_alert_level(SERP),
+			Some(Ratio::saturating_from_rational(80, 100))
+		);
+
+		// no more alert for the same threshold
+		System::reset_events();
+		CDPEngineModule::happened(&(SERP, 0, 85000));
+		assert!(System::events().is_empty());
+
+		CDPEngineModule::happened(&(SERP, 0, 100000));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::DebitCapUtilisationAlert {
+			collateral_type: SERP,
+			threshold: Ratio::one(),
+			total_debit_value: 10000,
+			maximum_total_debit_value: 10000,
+		}));
+
+		// the alert level is reset after utilisation drops
+		CDPEngineModule::happened(&(SERP, 0, 50000));
+		assert_eq!(CDPEngineModule::debit_cap_alert_level(SERP), None);
+		CDPEngineModule::happened(&(SERP, 0, 90000));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::DebitCapUtilisationAlert {
+			collateral_type: SERP,
+			threshold: Ratio::saturating_from_rational(90, 100),
+			total_debit_value: 9000,
+			maximum_total_debit_value: 10000,
+		}));
+	});
+}
+
+#[test]
+fn mint_rate_limit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::set_mint_rate_limit(Origin::signed(5), SERP, Some(100)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_mint_rate_limit(Origin::signed(1), SETUSD, Some(100)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_ok!(CDPEngineModule::set_mint_rate_limit(Origin::signed(1), SERP, Some(100)));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::MintRateLimitUpdated {
+			collateral_type: SERP,
+			new_mint_rate_limit: Some(100),
+		}));
+
+		// the total debit at the start of block is recorded on the first debit update
+		assert_eq!(CDPEngineModule::block_start_total_debit(SERP), None);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 500, 1000));
+		assert_eq!(CDPEngineModule::block_start_total_debit(SERP), Some((1, 0)));
+
+		System::set_block_number(2);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 0, 1000));
+		assert_eq!(CDPEngineModule::block_start_total_debit(SERP), Some((2, 1000)));
+		assert_noop!(
+			CDPEngineModule::adjust_position(&BOB, SERP, 500, 10),
+			Error::<Runtime>::ExceedMintRateLimit
+		);
+		// repay debit releases the quota of current block
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 0, -500));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, SERP, 500, 500));
+
+		System::set_block_number(3);
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, SERP, 0, 1000));
+
+		assert_ok!(CDPEngineModule::set_mint_rate_limit(Origin::signed(1), SERP, None));
+		assert_eq!(CDPEngineModule::mint_rate_limit(SERP), None);
+		assert_eq!(CDPEngineModule::block_start_total_debit(SERP), None);
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, SERP, 0, 1000));
+	});
+}
+
 #[test]
 fn check_position_valid_failed_when_invalid_feed_price() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -348,6 +573,128 @@ fn adjust_position_work() {
 	});
 }
 
+#[test]
+fn set_debit_currency_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			CDPEngineModule::set_debit_currency(Origin::signed(5), SERP, Some(SETR)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_debit_currency(Origin::signed(1), SETUSD, Some(SETR)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::set_debit_currency(Origin::signed(1), SERP, Some(DNAR)),
+			Error::<Runtime>::InvalidStableCurrencyType
+		);
+
+		assert_eq!(CDPEngineModule::debit_currency_id(SERP), SETUSD);
+		assert_ok!(CDPEngineModule::set_debit_currency(Origin::signed(1), SERP, Some(SETR)));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::DebitCurrencyUpdated {
+			collateral_type: SERP,
+			new_debit_currency: Some(SETR),
+		}));
+		assert_eq!(CDPEngineModule::debit_currency_id(SERP), SETR);
+
+		// debit of SERP is issued in SETR
+		let setr_before = Currencies::free_balance(SETR, &ALICE);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 500));
+		assert_eq!(Currencies::free_balance(SETR, &ALICE), setr_before + 50);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
+
+		// cannot change debit currency while there's debit
+		assert_noop!(
+			CDPEngineModule::set_debit_currency(Origin::signed(1), SERP, None),
+			Error::<Runtime>::DebitCurrencyInUse
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 0, -500));
+		assert_eq!(Currencies::free_balance(SETR, &ALICE), setr_before);
+		assert_ok!(CDPEngineModule::set_debit_currency(Origin::signed(1), SERP, None));
+		assert_eq!(CDPEngineModule::debit_currency_id(SERP), SETUSD);
+	});
+}
+
+#[test]
+fn set_mint_fee_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_mint_fee_rate(Origin::signed(5), SERP, Rate::saturating_from_rational(1, 100)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_mint_fee_rate(Origin::signed(1), SETUSD, Rate::saturating_from_rational(1, 100)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::set_mint_fee_rate(Origin::signed(1), SERP, Rate::one()),
+			Error::<Runtime>::InvalidMintFeeRate
+		);
+
+		assert_eq!(CDPEngineModule::mint_fee_rate(SERP), Rate::zero());
+		assert_ok!(CDPEngineModule::set_mint_fee_rate(
+			Origin::signed(1),
+			SERP,
+			Rate::saturating_from_rational(1, 100)
+		));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::MintFeeRateUpdated {
+			collateral_type: SERP,
+			new_mint_fee_rate: Rate::saturating_from_rational(1, 100),
+		}));
+		assert_eq!(
+			CDPEngineModule::mint_fee_rate(SERP),
+			Rate::saturating_from_rational(1, 100)
+		);
+	});
+}
+
+#[test]
+fn adjust_position_charge_mint_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_mint_fee_rate(
+			Origin::signed(1),
+			SERP,
+			Rate::saturating_from_rational(1, 10)
+		));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 500));
+		System::assert_has_event(Event::LoansModule(loans::Event::MintFeeCharged {
+			owner: ALICE,
+			collateral_type: SERP,
+			fee_amount: 5,
+		}));
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 45);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 5);
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 500);
+
+		// no fee is charged when paying back debit
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 0, -200));
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 25);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 5);
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 300);
+	});
+}
+
 // #[test]
 // fn expand_position_collateral_work() {
 // 	ExtBuilder::default().build().execute_with(|| {
@@ -442,108 +789,108 @@ fn adjust_position_work() {
 // 	});
 // }
 
-// #[test]
-// fn expand_position_collateral_for_lp_setusd_dnar_work() {
-// 	ExtBuilder::default().build().execute_with(|| {
-// 		assert_ok!(DEXModule::add_liquidity(
-// 			Origin::signed(CAROL),
-// 			SETUSD,
-// 			DNAR,
-// 			10000,
-// 			1000,
-// 			0
-// 		));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
-// 		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
-
-// 		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
-// 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
-// 			Change::NewValue(10000),
-// 		));
-// 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 2000));
-// 		assert_eq!(
-// 			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
-// 			Position {
-// 				collateral: 1000,
-// 				debit: 2000
-// 			}
-// 		);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 200);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
-// 		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
-// 		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 10000));
-
-// 		assert_noop!(
-// 			CDPEngineModule::expand_position_collateral(&ALICE, LP_SETUSD_DNAR, 200, 200),
-// 			dex::Error::<Runtime>::UnacceptableShareIncrement
-// 		);
-
-// 		assert_ok!(CDPEngineModule::expand_position_collateral(
-// 			&ALICE,
-// 			LP_SETUSD_DNAR,
-// 			300,
-// 			100
-// 		));
-// 		assert_eq!(
-// 			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
-// 			Position {
-// 				collateral: 1283,
-// 				debit: 5000
-// 			}
-// 		);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 206);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1283);
-// 		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
-// 		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 10294));
-// 	});
-// }
-
 #[test]
-fn shrink_position_debit_work() {
+fn expand_position_collateral_for_lp_setusd_dnar_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(10, 1)));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SETUSD,
+			DNAR,
+			10000,
+			1000,
+			0
+		));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
+		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
+
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
-			DNAR,
+			LP_SETUSD_DNAR,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
 		));
-		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DNAR, 100, 5000));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 2000));
 		assert_eq!(
-			LoansModule::positions(DNAR, ALICE),
+			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
 			Position {
-				collateral: 100,
-				debit: 5000
+				collateral: 1000,
+				debit: 2000
 			}
 		);
-		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 900);
-		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 200);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 100);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
+		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
 		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 10000));
 
-		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(8, 1)));
 		assert_noop!(
-			CDPEngineModule::shrink_position_debit(&ALICE, DNAR, 10, 0),
-			Error::<Runtime>::CannotSwap
+			CDPEngineModule::expand_position_collateral(&ALICE, LP_SETUSD_DNAR, 200, 200),
+			dex::Error::<Runtime>::UnacceptableShareIncrement
+		);
+
+		assert_ok!(CDPEngineModule::expand_position_collateral(
+			&ALICE,
+			LP_SETUSD_DNAR,
+			300,
+			100
+		));
+		assert_eq!(
+			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
+			Position {
+				collateral: 1283,
+				debit: 5000
+			}
+		);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 206);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1283);
+		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 10294));
+	});
+}
+
+#[test]
+fn shrink_position_debit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(10, 1)));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			DNAR,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DNAR, 100, 5000));
+		assert_eq!(
+			LoansModule::positions(DNAR, ALICE),
+			Position {
+				collateral: 100,
+				debit: 5000
+			}
+		);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 100);
+		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
+
+		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(8, 1)));
+		assert_noop!(
+			CDPEngineModule::shrink_position_debit(&ALICE, DNAR, 10, 0),
+			Error::<Runtime>::CannotSwap
 		);
 
 		assert_ok!(DEXModule::add_liquidity(
@@ -594,90 +941,91 @@ fn shrink_position_debit_work() {
 	});
 }
 
-// #[test]
-// fn shrink_position_debit_for_lp_setusd_dnar_work() {
-// 	ExtBuilder::default().build().execute_with(|| {
-// 		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
-// 		assert_ok!(DEXModule::add_liquidity(
-// 			Origin::signed(CAROL),
-// 			SETUSD,
-// 			DNAR,
-// 			10000,
-// 			1000,
-// 			0
-// 		));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
-// 		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
+#[test]
+fn shrink_position_debit_for_lp_setusd_dnar_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(10, 1)));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SETUSD,
+			DNAR,
+			10000,
+			1000,
+			0
+		));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
+		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
 
-// 		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
-// 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
-// 			Change::NewValue(10000),
-// 		));
-// 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
-// 		assert_eq!(
-// 			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
-// 			Position {
-// 				collateral: 1000,
-// 				debit: 5000
-// 			}
-// 		);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
-// 		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
-// 		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 10000));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
+		assert_eq!(
+			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
+			Position {
+				collateral: 1000,
+				debit: 5000
+			}
+		);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
+		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 10000));
 
-// 		assert_noop!(
-// 			CDPEngineModule::shrink_position_debit(&ALICE, LP_SETUSD_DNAR, 200, 200),
-// 			Error::<Runtime>::NotEnoughDebitDecrement
-// 		);
+		assert_noop!(
+			CDPEngineModule::shrink_position_debit(&ALICE, LP_SETUSD_DNAR, 200, 200),
+			Error::<Runtime>::NotEnoughDebitDecrement
+		);
 
-// 		assert_ok!(CDPEngineModule::shrink_position_debit(&ALICE, LP_SETUSD_DNAR, 100, 80));
-// 		assert_eq!(
-// 			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
-// 			Position {
-// 				collateral: 900,
-// 				debit: 4010
-// 			}
-// 		);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 900);
-// 		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
-// 		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 9901));
+		assert_ok!(CDPEngineModule::shrink_position_debit(&ALICE, LP_SETUSD_DNAR, 100, 80));
+		assert_eq!(
+			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
+			Position {
+				collateral: 900,
+				debit: 4010
+			}
+		);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 900);
+		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 9901));
 
-// 		assert_ok!(CDPEngineModule::shrink_position_debit(&ALICE, LP_SETUSD_DNAR, 600, 500));
-// 		assert_eq!(
-// 			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
-// 			Position {
-// 				collateral: 300,
-// 				debit: 0
-// 			}
-// 		);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 685);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 300);
-// 		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
-// 		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 9315));
-// 	});
-// }
+		assert_ok!(CDPEngineModule::shrink_position_debit(&ALICE, LP_SETUSD_DNAR, 600, 500));
+		assert_eq!(
+			LoansModule::positions(LP_SETUSD_DNAR, ALICE),
+			Position {
+				collateral: 300,
+				debit: 0
+			}
+		);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 685);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 300);
+		assert_eq!(Currencies::free_balance(DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &LoansModule::account_id()), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(DNAR, SETUSD), (1000, 9315));
+	});
+}
 
 #[test]
 fn remain_debit_value_too_small_check() {
@@ -748,6 +1096,38 @@ fn remain_debit_value_too_small_check() {
 // 	});
 // }
 
+#[test]
+fn liquidate_unsafe_cdp_of_setr_debit_by_collateral_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_debit_currency(Origin::signed(1), SERP, Some(SETR)));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 500));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// SERP cannot be swapped to SETR, the debit is moved to SETUSD in which the auction settles
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, SERP));
+		assert_eq!(AUCTION.with(|v| *v.borrow()), Some((ALICE, SERP, 100, 60)));
+		assert_eq!(CDPTreasuryModule::debit_pool_of(SETR), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool_of(SETUSD), 50);
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 0);
+	});
+}
+
 #[test]
 fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -785,6 +1165,15 @@ fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 			Change::NoChange,
 		));
 
+		assert_ok!(CDPTreasuryModule::set_insurance_fund_params(
+			Origin::signed(1),
+			cdp_treasury::InsuranceFundParams {
+				penalty_share: Ratio::saturating_from_rational(1, 2),
+				surplus_share: Ratio::zero(),
+				cap: 100,
+			}
+		));
+
 		// pool is enough, but slippage limit the swap
 		MockPriceSource::set_price(SERP, Some(Price::saturating_from_rational(2, 1)));
 		assert_eq!(
@@ -806,6 +1195,9 @@ fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (100, 121));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		// the penalty is realised by the auction proceeds
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 0);
+		assert_eq!(CDPTreasuryModule::liquidation_penalties_in_auction(SERP), (10, 60));
 		assert_eq!(Currencies::free_balance(SERP, &ALICE), 900);
 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 50);
 		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 0);
@@ -849,6 +1241,14 @@ fn liquidate_unsafe_cdp_by_swap() {
 			Change::NoChange,
 			Change::NoChange,
 		));
+		assert_ok!(CDPTreasuryModule::set_insurance_fund_params(
+			Origin::signed(1),
+			cdp_treasury::InsuranceFundParams {
+				penalty_share: Ratio::saturating_from_rational(1, 2),
+				surplus_share: Ratio::zero(),
+				cap: 100,
+			}
+		));
 
 		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, SERP));
 		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
@@ -861,6 +1261,8 @@ fn liquidate_unsafe_cdp_by_swap() {
 
 		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (199, 61));
 		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		// the penalty realised by the swap contributes to the insurance fund
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 5);
 		assert_eq!(Currencies::free_balance(SERP, &ALICE), 900);
 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 50);
 		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 0);
@@ -868,266 +1270,571 @@ fn liquidate_unsafe_cdp_by_swap() {
 	});
 }
 
-// #[test]
-// fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_swap_dnar() {
-// 	ExtBuilder::default().build().execute_with(|| {
-// 		System::set_block_number(1);
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
-// 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
-// 			Change::NewValue(10000),
-// 		));
-// 		assert_ok!(DEXModule::add_liquidity(
-// 			Origin::signed(CAROL),
-// 			SETUSD,
-// 			DNAR,
-// 			10000,
-// 			500,
-// 			0
-// 		));
-// 		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (10000, 500));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
-// 		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
-
-// 		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
-// 		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
-
-// 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 5000);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 1000);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(
-// 			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
-// 			0
-// 		);
-// 		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
-
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::max_value())),
-// 			Change::NoChange,
-// 			Change::NoChange,
-// 			Change::NoChange,
-// 		));
+#[test]
+fn liquidate_unsafe_cdp_when_operation_paused() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SERP,
+			SETUSD,
+			100,
+			121,
+			0
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 500));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
 
-// 		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, LP_SETUSD_DNAR));
-// 		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
-// 			collateral_type: LP_SETUSD_DNAR,
-// 			owner: ALICE,
-// 			collateral_amount: 1000,
-// 			bad_debt_value: 500,
-// 			target_amount: 600,
-// 		}));
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::Liquidate,
+			Some(SERP),
+			None
+		));
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, SERP),
+			cdp_treasury::Error::<Runtime>::OperationPaused
+		);
+		assert_ok!(CDPTreasuryModule::unpause_operation(
+			Origin::signed(1),
+			PausableOperation::Liquidate,
+			Some(SERP)
+		));
 
-// 		assert_eq!(
-// 			MockPriceSource::get_relative_price(SETUSD, DNAR),
-// 			Price::checked_from_rational(1, 20)
-// 		);
-// 		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (9400, 481));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 19000);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1019);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 500);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 600);
-// 		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(
-// 			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
-// 			0
-// 		);
-// 		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
-// 	});
-// }
+		// liquidation by DEX is paused, collateral auction is created instead
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::LiquidateByDex,
+			None,
+			None
+		));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, SERP));
+		assert_eq!(DEXModule::get_liquidity_pool(SERP, SETUSD), (100, 121));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(SERP, ALICE).collateral, 0);
+	});
+}
 
-// #[test]
-// fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_setusd_take_whole_target() {
-// 	ExtBuilder::default().build().execute_with(|| {
-// 		System::set_block_number(1);
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
-// 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
-// 			Change::NewValue(10000),
-// 		));
-// 		assert_ok!(DEXModule::add_liquidity(
-// 			Origin::signed(CAROL),
-// 			SETUSD,
-// 			DNAR,
-// 			10000,
-// 			500,
-// 			0
-// 		));
-// 		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (10000, 500));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
-// 		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
-
-// 		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
-// 		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
-
-// 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 2000));
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 200);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 2000);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 1000);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(
-// 			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
-// 			0
-// 		);
-// 		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
+#[test]
+fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_swap_dnar() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SETUSD,
+			DNAR,
+			10000,
+			500,
+			0
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (10000, 500));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
+		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
 
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::max_value())),
-// 			Change::NoChange,
-// 			Change::NoChange,
-// 			Change::NoChange,
-// 		));
+		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
 
-// 		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, LP_SETUSD_DNAR));
-// 		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
-// 			collateral_type: LP_SETUSD_DNAR,
-// 			owner: ALICE,
-// 			collateral_amount: 1000,
-// 			bad_debt_value: 200,
-// 			target_amount: 240,
-// 		}));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 5000);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 1000);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
 
-// 		assert_eq!(
-// 			MockPriceSource::get_relative_price(SETUSD, DNAR),
-// 			Price::checked_from_rational(1, 20)
-// 		);
-// 		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (9500, 475));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 19000);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1025);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 460);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 240);
-// 		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(
-// 			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
-// 			0
-// 		);
-// 		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
-// 	});
-// }
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
 
-// #[test]
-// fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_create_dnar_auction() {
-// 	ExtBuilder::default().build().execute_with(|| {
-// 		System::set_block_number(1);
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
-// 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-// 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
-// 			Change::NewValue(10000),
-// 		));
-// 		assert_ok!(DEXModule::add_liquidity(
-// 			Origin::signed(CAROL),
-// 			SETUSD,
-// 			DNAR,
-// 			500,
-// 			25,
-// 			0
-// 		));
-// 		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (500, 25));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 1000);
-// 		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
-
-// 		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
-// 		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
-
-// 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 5000);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 1000);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
-// 		assert_eq!(
-// 			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
-// 			0
-// 		);
-// 		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, LP_SETUSD_DNAR));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: LP_SETUSD_DNAR,
+			owner: ALICE,
+			collateral_amount: 1000,
+			bad_debt_value: 500,
+			target_amount: 600,
+		}));
 
-// 		assert_ok!(CDPEngineModule::set_collateral_params(
-// 			Origin::signed(1),
-// 			LP_SETUSD_DNAR,
-// 			Change::NewValue(Some(Ratio::max_value())),
-// 			Change::NoChange,
-// 			Change::NoChange,
-// 			Change::NoChange,
-// 		));
+		assert_eq!(
+			MockPriceSource::get_relative_price(SETUSD, DNAR),
+			Price::checked_from_rational(1, 20)
+		);
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (9400, 481));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 19000);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1019);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 500);
+		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 600);
+		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
+	});
+}
 
-// 		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, LP_SETUSD_DNAR));
-// 		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
-// 			collateral_type: LP_SETUSD_DNAR,
-// 			owner: ALICE,
-// 			collateral_amount: 1000,
-// 			bad_debt_value: 500,
-// 			target_amount: 600,
-// 		}));
+#[test]
+fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_setusd_take_whole_target() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SETUSD,
+			DNAR,
+			10000,
+			500,
+			0
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (10000, 500));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 20000);
+		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
 
-// 		assert_eq!(
-// 			MockPriceSource::get_relative_price(SETUSD, DNAR),
-// 			Price::checked_from_rational(1, 20)
-// 		);
-// 		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (0, 0));
-// 		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
-// 		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
-// 		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
-// 		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 0);
-// 		assert_eq!(CDPTreasuryModule::debit_pool(), 500);
-// 		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 500);
-// 		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 25);
-// 		assert_eq!(
-// 			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
-// 			0
-// 		);
-// 		assert_eq!(AUCTION.with(|v| *v.borrow()), Some((ALICE, DNAR, 25, 100)));
-// 	});
-// }
+		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 2000));
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 200);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 2000);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 1000);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, LP_SETUSD_DNAR));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: LP_SETUSD_DNAR,
+			owner: ALICE,
+			collateral_amount: 1000,
+			bad_debt_value: 200,
+			target_amount: 240,
+		}));
+
+		assert_eq!(
+			MockPriceSource::get_relative_price(SETUSD, DNAR),
+			Price::checked_from_rational(1, 20)
+		);
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (9500, 475));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 19000);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1025);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 460);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
+		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 240);
+		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_create_dnar_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SETUSD,
+			DNAR,
+			500,
+			25,
+			0
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (500, 25));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 1000);
+		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 0);
+
+		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 5000);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 1000);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), None);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, LP_SETUSD_DNAR));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: LP_SETUSD_DNAR,
+			owner: ALICE,
+			collateral_amount: 1000,
+			bad_debt_value: 500,
+			target_amount: 600,
+		}));
+
+		assert_eq!(
+			MockPriceSource::get_relative_price(SETUSD, DNAR),
+			Price::checked_from_rational(1, 20)
+		);
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (0, 0));
+		assert_eq!(Currencies::total_issuance(LP_SETUSD_DNAR), 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(DNAR, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 500);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
+		assert_eq!(Currencies::free_balance(LP_SETUSD_DNAR, &LoansModule::account_id()), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 500);
+		assert_eq!(Currencies::free_balance(SETUSD, &CDPTreasuryModule::account_id()), 500);
+		assert_eq!(Currencies::free_balance(DNAR, &CDPTreasuryModule::account_id()), 25);
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), Some((ALICE, DNAR, 25, 100)));
+	});
+}
+
+#[test]
+fn get_lp_components_withdrawn_limit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(10, 1)));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Some(Price::saturating_from_rational(1, 1)));
+		assert_eq!(
+			CDPEngineModule::get_lp_components_withdrawn_limit(LP_SETUSD_DNAR, 100),
+			Ok((25, 2))
+		);
+
+		assert_noop!(
+			CDPEngineModule::get_lp_components_withdrawn_limit(DNAR, 100),
+			Error::<Runtime>::InvalidCollateralType
+		);
+
+		MockPriceSource::set_price(LP_SETUSD_DNAR, None);
+		assert_noop!(
+			CDPEngineModule::get_lp_components_withdrawn_limit(LP_SETUSD_DNAR, 100),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+	});
+}
+
+#[test]
+fn split_target_by_value_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockPriceSource::set_price(DNAR, Some(Price::saturating_from_rational(10, 1)));
+		assert_eq!(
+			CDPEngineModule::split_target_by_value(SETUSD, SERP, 300, DNAR, 10, 100),
+			Ok((75, 25))
+		);
+		assert_eq!(
+			CDPEngineModule::split_target_by_value(SETUSD, SERP, 100, DNAR, 10, 101),
+			Ok((50, 51))
+		);
+		assert_eq!(CDPEngineModule::split_target_by_value(SETUSD, SERP, 0, DNAR, 0, 100), Ok((50, 50)));
+
+		MockPriceSource::set_price(DNAR, None);
+		assert_noop!(
+			CDPEngineModule::split_target_by_value(SETUSD, SERP, 300, DNAR, 10, 100),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_of_lp_setusd_dnar_and_create_lp_auction_when_withdrawn_slippage_exceeded() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			SETUSD,
+			DNAR,
+			10000,
+			500,
+			0
+		));
+		assert_ok!(Currencies::transfer(Origin::signed(CAROL), ALICE, LP_SETUSD_DNAR, 1000));
+
+		MockPriceSource::set_price(DNAR, Price::checked_from_rational(20, 1));
+		MockPriceSource::set_price(LP_SETUSD_DNAR, Price::checked_from_rational(1, 1));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, LP_SETUSD_DNAR, 1000, 5000));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			LP_SETUSD_DNAR,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// the pool is far away from the oracle price of DNAR, DNAR withdrawn is below the limit,
+		// so the lp collateral is auctioned without removing the liquidity.
+		MockPriceSource::set_price(DNAR, Price::checked_from_rational(1, 1));
+		assert_ok!(CDPEngineModule::liquidate(Origin::none(), LP_SETUSD_DNAR, ALICE));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: LP_SETUSD_DNAR,
+			owner: ALICE,
+			collateral_amount: 1000,
+			bad_debt_value: 500,
+			target_amount: 600,
+		}));
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(LP_SETUSD_DNAR, ALICE).collateral, 0);
+		assert_eq!(DEXModule::get_liquidity_pool(SETUSD, DNAR), (10000, 500));
+		assert_eq!(
+			Currencies::free_balance(LP_SETUSD_DNAR, &CDPTreasuryModule::account_id()),
+			1000
+		);
+		assert_eq!(AUCTION.with(|v| *v.borrow()), Some((ALICE, LP_SETUSD_DNAR, 1000, 600)));
+	});
+}
+
+#[test]
+fn liquidate_batch_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 500));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, SERP, 100, 100));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_noop!(
+			CDPEngineModule::liquidate_batch(Origin::none(), vec![]),
+			Error::<Runtime>::InvalidBatchSize
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate_batch(Origin::none(), vec![(SERP, ALICE); 51]),
+			Error::<Runtime>::InvalidBatchSize
+		);
+
+		// the batch is valid as long as any CDP of it is unsafe
+		assert!(CDPEngineModule::validate_unsigned(
+			TransactionSource::Local,
+			&crate::Call::liquidate_batch(vec![(SERP, BOB), (SERP, ALICE)])
+		)
+		.is_ok());
+
+		// the batch is invalid when liquidation of the unsafe CDPs is paused
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::Liquidate,
+			Some(SERP),
+			None
+		));
+		assert!(CDPEngineModule::validate_unsigned(
+			TransactionSource::Local,
+			&crate::Call::liquidate_batch(vec![(SERP, BOB), (SERP, ALICE)])
+		)
+		.is_err());
+		assert_ok!(CDPTreasuryModule::unpause_operation(
+			Origin::signed(1),
+			PausableOperation::Liquidate,
+			Some(SERP)
+		));
+
+		// bob is safe and fails, alice is still liquidated
+		let result = CDPEngineModule::liquidate_batch(Origin::none(), vec![(SERP, BOB), (SERP, ALICE)]);
+		assert_eq!(
+			result.unwrap().actual_weight,
+			Some(
+				<() as WeightInfo>::liquidate_by_dex()
+					+ <Runtime as frame_system::Config>::DbWeight::get().reads(4)
+			)
+		);
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::LiquidateCDPFailed {
+			collateral_type: SERP,
+			owner: BOB,
+			error: Error::<Runtime>::MustBeUnsafe.into(),
+		}));
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: SERP,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			target_amount: 60,
+		}));
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(SERP, ALICE).collateral, 0);
+		assert_eq!(LoansModule::positions(SERP, BOB).debit, 100);
+		assert_eq!(LoansModule::positions(SERP, BOB).collateral, 100);
+		assert!(CDPEngineModule::validate_unsigned(
+			TransactionSource::Local,
+			&crate::Call::liquidate_batch(vec![(SERP, BOB), (SERP, ALICE)])
+		)
+		.is_err());
+
+		mock_shutdown();
+		assert_noop!(
+			CDPEngineModule::liquidate_batch(Origin::none(), vec![(SERP, BOB)]),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
+#[test]
+fn settle_batch_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, SERP, 100, 0));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, SERP, 100, 100));
+		assert_noop!(
+			CDPEngineModule::settle_batch(Origin::none(), vec![(SERP, BOB)]),
+			Error::<Runtime>::MustAfterShutdown
+		);
+
+		mock_shutdown();
+		assert!(CDPEngineModule::validate_unsigned(
+			TransactionSource::Local,
+			&crate::Call::settle_batch(vec![(SERP, ALICE), (SERP, BOB)])
+		)
+		.is_ok());
+
+		// alice has no debit and fails, bob is still settled
+		let result = CDPEngineModule::settle_batch(Origin::none(), vec![(SERP, ALICE), (SERP, BOB)]);
+		assert_eq!(
+			result.unwrap().actual_weight,
+			Some(<() as WeightInfo>::settle() + <Runtime as frame_system::Config>::DbWeight::get().reads(4))
+		);
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::SettleCDPFailed {
+			collateral_type: SERP,
+			owner: ALICE,
+			error: Error::<Runtime>::NoDebitValue.into(),
+		}));
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::SettleCDPInDebit {
+			collateral_type: SERP,
+			owner: BOB,
+		}));
+		assert_eq!(LoansModule::positions(SERP, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(SERP, BOB).debit, 0);
+		assert_eq!(LoansModule::positions(SERP, BOB).collateral, 90);
+	});
+}
 
 #[test]
 fn settle_cdp_has_debit_work() {
@@ -1365,8 +2072,9 @@ fn offchain_worker_works_cdp() {
 		// offchain worker will liquidate alice
 		let tx = pool_state.write().transactions.pop().unwrap();
 		let tx = Extrinsic::decode(&mut &*tx).unwrap();
-		if let MockCall::CDPEngineModule(crate::Call::liquidate(currency_call, who_call)) = tx.call {
-			assert_ok!(CDPEngineModule::liquidate(Origin::none(), currency_call, who_call));
+		if let MockCall::CDPEngineModule(crate::Call::liquidate_batch(cdps)) = tx.call {
+			assert_eq!(cdps, vec![(SERP, ALICE)]);
+			assert_ok!(CDPEngineModule::liquidate_batch(Origin::none(), cdps));
 		}
 		// empty offchain tx pool (Bob was not liquidated)
 		assert!(pool_state.write().transactions.pop().is_none());
@@ -1383,8 +2091,9 @@ fn offchain_worker_works_cdp() {
 		// offchain worker will settle bob's position
 		let tx = pool_state.write().transactions.pop().unwrap();
 		let tx = Extrinsic::decode(&mut &*tx).unwrap();
-		if let MockCall::CDPEngineModule(crate::Call::settle(currency_call, who_call)) = tx.call {
-			assert_ok!(CDPEngineModule::settle(Origin::none(), currency_call, who_call));
+		if let MockCall::CDPEngineModule(crate::Call::settle_batch(cdps)) = tx.call {
+			assert_eq!(cdps, vec![(SERP, BOB)]);
+			assert_ok!(CDPEngineModule::settle_batch(Origin::none(), cdps));
 		}
 		// emergency shutdown settles bob's debit position
 		assert_eq!(LoansModule::positions(SERP, BOB).debit, 0);
@@ -1428,8 +2137,9 @@ fn offchain_worker_iteration_limit_works() {
 		run_to_block_offchain(2);
 		let tx = pool_state.write().transactions.pop().unwrap();
 		let tx = Extrinsic::decode(&mut &*tx).unwrap();
-		if let MockCall::CDPEngineModule(crate::Call::liquidate(currency_call, who_call)) = tx.call {
-			assert_ok!(CDPEngineModule::liquidate(Origin::none(), currency_call, who_call));
+		if let MockCall::CDPEngineModule(crate::Call::liquidate_batch(cdps)) = tx.call {
+			assert_eq!(cdps, vec![(SERP, ALICE)]);
+			assert_ok!(CDPEngineModule::liquidate_batch(Origin::none(), cdps));
 		}
 		// alice is liquidated but not bob, he will get liquidated next block due to iteration limit
 		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 0);
@@ -1441,8 +2151,9 @@ fn offchain_worker_iteration_limit_works() {
 		run_to_block_offchain(3);
 		let tx = pool_state.write().transactions.pop().unwrap();
 		let tx = Extrinsic::decode(&mut &*tx).unwrap();
-		if let MockCall::CDPEngineModule(crate::Call::liquidate(currency_call, who_call)) = tx.call {
-			assert_ok!(CDPEngineModule::liquidate(Origin::none(), currency_call, who_call));
+		if let MockCall::CDPEngineModule(crate::Call::liquidate_batch(cdps)) = tx.call {
+			assert_eq!(cdps, vec![(SERP, BOB)]);
+			assert_ok!(CDPEngineModule::liquidate_batch(Origin::none(), cdps));
 		}
 		assert_eq!(LoansModule::positions(SERP, BOB).debit, 0);
 		assert_eq!(LoansModule::positions(SERP, BOB).collateral, 0);
@@ -1489,10 +2200,11 @@ fn offchain_default_max_iterator_works() {
 			Change::NoChange,
 		));
 		run_to_block_offchain(2);
-		// should only run 1000 iterations stopping due to DEFAULT_MAX_ITERATIONS
-		assert_eq!(pool_state.write().transactions.len(), 1000);
+		// should only run 1000 iterations stopping due to DEFAULT_MAX_ITERATIONS,
+		// found CDPs are submitted in batches of MaxBatchSize
+		assert_eq!(pool_state.write().transactions.len(), 20);
 		// should only now run 1 iteration to finish off where it ended last block
 		run_to_block_offchain(3);
-		assert_eq!(pool_state.write().transactions.len(), 1001);
+		assert_eq!(pool_state.write().transactions.len(), 21);
 	});
 }
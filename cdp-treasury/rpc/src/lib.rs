@@ -0,0 +1,106 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+//
+// This file is part of Ethical DeFi.
+//
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: BUSL-1.1 (Business Source License 1.1)
+
+//! RPC interface for the CDP treasury module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use primitives::CurrencyId;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use cdp_treasury_runtime_api::{
+	BadDebtReport, BalanceSheet, CDPTreasuryApi as CDPTreasuryRuntimeApi, CollateralHolding,
+};
+
+#[rpc]
+pub trait CDPTreasuryApi<BlockHash, BlockNumber> {
+	/// Get the bad debt reports of all collateral types.
+	#[rpc(name = "cdpTreasury_getBadDebtReports")]
+	fn get_bad_debt_reports(&self, at: Option<BlockHash>) -> Result<Vec<(CurrencyId, BadDebtReport)>>;
+
+	/// Get the current balance sheet of CDP treasury with oracle valuations.
+	#[rpc(name = "cdpTreasury_getBalanceSheet")]
+	fn get_balance_sheet(&self, at: Option<BlockHash>) -> Result<BalanceSheet<BlockNumber>>;
+
+	/// Get the historical snapshots of the balance sheet, from the oldest to
+	/// the latest.
+	#[rpc(name = "cdpTreasury_getBalanceSheetSnapshots")]
+	fn get_balance_sheet_snapshots(&self, at: Option<BlockHash>) -> Result<Vec<BalanceSheet<BlockNumber>>>;
+}
+
+/// A struct that implements the [`CDPTreasuryApi`].
+pub struct CDPTreasury<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> CDPTreasury<C, B> {
+	/// Create new `CDPTreasury` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, BlockNumber> CDPTreasuryApi<<Block as BlockT>::Hash, BlockNumber> for CDPTreasury<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: CDPTreasuryRuntimeApi<Block, BlockNumber>,
+	BlockNumber: Codec,
+{
+	fn get_bad_debt_reports(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(CurrencyId, BadDebtReport)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.bad_debt_reports(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get bad debt reports.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_balance_sheet(&self, at: Option<<Block as BlockT>::Hash>) -> Result<BalanceSheet<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.balance_sheet(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get balance sheet.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_balance_sheet_snapshots(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<BalanceSheet<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.balance_sheet_snapshots(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get balance sheet snapshots.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
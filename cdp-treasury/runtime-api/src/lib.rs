@@ -0,0 +1,39 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+//
+// This file is part of Ethical DeFi.
+//
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: BUSL-1.1 (Business Source License 1.1)
+
+//! Runtime API definition for the CDP treasury module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use primitives::CurrencyId;
+use sp_std::prelude::*;
+
+pub use cdp_treasury::{BadDebtReport, BalanceSheet, CollateralHolding};
+
+sp_api::decl_runtime_apis! {
+	pub trait CDPTreasuryApi<BlockNumber> where
+		BlockNumber: Codec,
+	{
+		/// Get the bad debt and recovered stable currency of `currency_id`.
+		fn bad_debt_report(currency_id: CurrencyId) -> BadDebtReport;
+
+		/// Get the bad debt reports of all collateral types.
+		fn bad_debt_reports() -> Vec<(CurrencyId, BadDebtReport)>;
+
+		/// Get the current balance sheet of CDP treasury with oracle valuations.
+		fn balance_sheet() -> BalanceSheet<BlockNumber>;
+
+		/// Get the historical snapshots of the balance sheet, from the oldest
+		/// to the latest.
+		fn balance_sheet_snapshots() -> Vec<BalanceSheet<BlockNumber>>;
+	}
+}
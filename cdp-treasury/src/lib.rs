@@ -21,12 +21,18 @@ use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
-	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber,
+	traits::{AccountIdConversion, One, Saturating, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+};
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Ratio, SerpTreasury,
+	SwapLimit,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio, SerpTreasury, SwapLimit};
 use sp_std::{prelude::*, vec};
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
 mod mock;
 mod tests;
 pub mod weights;
@@ -34,6 +40,338 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// Abstraction over the debit auction handler, which is used by CDP treasury
+/// to raise stable currency against its bad debt.
+pub trait DebitAuctionManager {
+	/// Create a debit auction which sells at most `initial_amount` of newly
+	/// issued `currency_id` for `fix` amount of stable currency.
+	fn new_debit_auction(currency_id: CurrencyId, initial_amount: Balance, fix: Balance) -> DispatchResult;
+
+	/// Get the total stable currency to be raised by all active debit auctions.
+	fn get_total_debit_in_auction() -> Balance;
+
+	/// Get the total amount of `currency_id` that may be issued by all active
+	/// debit auctions.
+	fn get_total_issuance_in_auction(currency_id: CurrencyId) -> Balance;
+}
+
+impl DebitAuctionManager for () {
+	fn new_debit_auction(_currency_id: CurrencyId, _initial_amount: Balance, _fix: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_total_debit_in_auction() -> Balance {
+		Zero::zero()
+	}
+
+	fn get_total_issuance_in_auction(_currency_id: CurrencyId) -> Balance {
+		Zero::zero()
+	}
+}
+
+/// Abstraction over the per-collateral bad debt accounting of CDP treasury.
+pub trait BadDebtManager {
+	/// Record the bad debt caused by `currency_id` into the debit pool of
+	/// `stable_currency_id`. The report counts it in the default stable
+	/// currency.
+	fn on_collateral_debit(currency_id: CurrencyId, stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+
+	/// Record `amount` of `stable_currency_id` recovered by selling the
+	/// liquidated `currency_id`. The report counts it in the default stable
+	/// currency.
+	fn on_collateral_recovered(currency_id: CurrencyId, stable_currency_id: CurrencyId, amount: Balance);
+}
+
+/// Abstraction over the stable proceeds of collateral auctions held by CDP
+/// treasury.
+pub trait AuctionProceedsManager<AccountId> {
+	/// Return `amount` stable currency recovered by selling `currency_id` to
+	/// `to`, as the excess proceeds above the debt and penalty.
+	fn return_excess_proceeds(currency_id: CurrencyId, to: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Record the `proceeds` of auctioning the collateral of
+	/// `refund_recipient` which cover its `debt_owed` as recovered by selling
+	/// `currency_id`, and contribute the liquidation penalty they realise.
+	/// The proceeds of the collateral owned by CDP treasury recover no bad
+	/// debt.
+	fn on_auction_proceeds(
+		currency_id: CurrencyId,
+		refund_recipient: &AccountId,
+		debt_owed: Balance,
+		proceeds: Balance,
+	);
+
+	/// Get the supply and target amount of swapping `currency_id` to the
+	/// default stable currency with `limit`, by the swap path which collateral
+	/// auctions are taken by DEX with.
+	fn get_collateral_swap_amount(currency_id: CurrencyId, limit: SwapLimit<Balance>) -> Option<(Balance, Balance)>;
+}
+
+/// Abstraction over the insurance fund of CDP treasury.
+pub trait InsuranceFundManager {
+	/// Contribute the configured share of liquidation `penalty` realised in
+	/// `stable_currency_id` to the insurance fund.
+	fn on_liquidation_penalty(stable_currency_id: CurrencyId, penalty: Balance);
+
+	/// Record the liquidation `penalty` included in the `target` of the
+	/// collateral auctions of `currency_id`, both in the default stable
+	/// currency. It is contributed as the auction proceeds cover the target.
+	fn on_liquidation_penalty_auctioned(currency_id: CurrencyId, penalty: Balance, target: Balance);
+}
+
+/// Abstraction over the registry of paused CDP operations.
+pub trait OperationPauseManager {
+	/// Ensure `operation` is paused neither for `currency_id` nor for all
+	/// collateral types.
+	fn ensure_operation_not_paused(operation: PausableOperation, currency_id: CurrencyId) -> DispatchResult;
+}
+
+/// Abstraction over the accounting of CDP treasury under each stable
+/// currency in which CDPs issue debit.
+pub trait MultiStableCurrencyTreasury<AccountId> {
+	/// Get all the stable currencies supported by the treasury, the default
+	/// one comes first.
+	fn get_stable_currency_ids() -> Vec<CurrencyId>;
+
+	/// Get the surplus pool under `stable_currency_id`.
+	fn get_surplus_pool_of(stable_currency_id: CurrencyId) -> Balance;
+
+	/// Get the debit pool under `stable_currency_id`.
+	fn get_debit_pool_of(stable_currency_id: CurrencyId) -> Balance;
+
+	/// Increase the debit pool under `stable_currency_id`.
+	fn on_system_debit_of(stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+
+	/// Move `amount` of debit out of the debit pool under
+	/// `from_stable_currency_id` into the debit pool under
+	/// `to_stable_currency_id` as `converted_amount`.
+	fn move_debit_of(
+		from_stable_currency_id: CurrencyId,
+		amount: Balance,
+		to_stable_currency_id: CurrencyId,
+		converted_amount: Balance,
+	) -> DispatchResult;
+
+	/// Increase the surplus pool under `stable_currency_id`.
+	fn on_system_surplus_of(stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+
+	/// Issue `debit` of `stable_currency_id` to `who`, the debit pool is
+	/// increased if it is not backed.
+	fn issue_debit_of(stable_currency_id: CurrencyId, who: &AccountId, debit: Balance, backed: bool) -> DispatchResult;
+
+	/// Burn `debit` of `stable_currency_id` from `who`.
+	fn burn_debit_of(stable_currency_id: CurrencyId, who: &AccountId, debit: Balance) -> DispatchResult;
+
+	/// Swap collateral to `stable_currency_id` by DEX.
+	fn swap_collateral_to_stable_of(
+		stable_currency_id: CurrencyId,
+		currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+		collateral_in_auction: bool,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError>;
+}
+
+/// Parameters of the insurance fund of CDP treasury.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct InsuranceFundParams {
+	/// The share of liquidation penalties contributed to the insurance fund.
+	pub penalty_share: Ratio,
+	/// The share of system surplus contributed to the insurance fund.
+	pub surplus_share: Ratio,
+	/// The cap of the insurance fund.
+	pub cap: Balance,
+}
+
+/// The bad debt report of a collateral type.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct BadDebtReport {
+	/// Total bad debt caused by the collateral type.
+	pub bad_debt: Balance,
+	/// Total stable currency recovered by selling the collateral type.
+	pub recovered: Balance,
+}
+
+impl BadDebtReport {
+	/// Return the loss of the collateral type not recovered yet.
+	pub fn net_loss(&self) -> Balance {
+		self.bad_debt.saturating_sub(self.recovered)
+	}
+}
+
+/// Parameters of the debit auctions created by CDP treasury to recapitalise
+/// its bad debt.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DebitAuctionParams {
+	/// The currency newly issued to the winners of debit auctions.
+	pub issuance_currency_id: CurrencyId,
+	/// Debit auctions are created only when the debit pool exceeds the
+	/// surplus pool and the debit already in auction by more than this.
+	pub trigger_threshold: Balance,
+	/// The fixed amount of stable currency raised by per lot.
+	pub lot_size: Balance,
+	/// The initial amount of issuance currency for sale per lot.
+	pub initial_issuance_per_lot: Balance,
+	/// The cap of the issuance currency for sale in all active debit
+	/// auctions.
+	pub max_issuance: Balance,
+}
+
+/// Policy of selling the collateral held by CDP treasury which is not in
+/// auction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CollateralSellPolicy {
+	/// Idle collateral is sold only when its amount reaches this threshold.
+	pub threshold: Balance,
+	/// The max amount of idle collateral sold per period.
+	pub max_sell_amount_per_period: Balance,
+	/// The max slippage of DEX price compared to oracle price, otherwise the
+	/// idle collateral is sold by collateral auctions.
+	pub max_swap_slippage: Ratio,
+}
+
+/// Guard of the DEX price against the oracle price when CDP treasury swaps
+/// collateral to stable currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SwapPriceGuard<BlockNumber> {
+	/// The max deviation of the DEX price below the oracle price.
+	pub max_deviation: Ratio,
+	/// The collateral amount sold per swap when a sell exceeding the guard is
+	/// split into multiple swaps, 0 disables the splitting.
+	pub twap_chunk_size: Balance,
+	/// The blocks between two swaps of a split sell.
+	pub twap_interval: BlockNumber,
+}
+
+/// Strategy of splitting collateral into multiple collateral auction lots.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum LotSplitStrategy {
+	/// Split into lots of equal amount, the count of which is decided by
+	/// `ExpectedCollateralAuctionSize`.
+	FixedSize,
+	/// Split into lots of equal amount, each worth about `lot_value` stable
+	/// currency by the oracle price.
+	ValueBased { lot_value: Balance },
+	/// Split into lots growing by `ratio` geometrically, the first of which
+	/// is `ExpectedCollateralAuctionSize`.
+	Geometric { ratio: Ratio },
+}
+
+impl Default for LotSplitStrategy {
+	fn default() -> Self {
+		LotSplitStrategy::FixedSize
+	}
+}
+
+/// Params of splitting collateral auctions of a collateral type.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct LotSplitParams<BlockNumber> {
+	/// The strategy of splitting collateral into lots.
+	pub strategy: LotSplitStrategy,
+	/// The blocks between the starts of two lots, 0 starts all lots at once.
+	pub stagger_interval: BlockNumber,
+}
+
+/// A collateral auction lot waiting to start.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingCollateralLot<AccountId> {
+	/// The receiver of the refund of the collateral auction.
+	pub refund_receiver: AccountId,
+	/// The collateral type.
+	pub currency_id: CurrencyId,
+	/// The collateral amount of the lot.
+	pub amount: Balance,
+	/// The target stable amount of the lot.
+	pub target: Balance,
+}
+
+/// The holding of a collateral type in CDP treasury.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CollateralHolding {
+	/// The collateral type.
+	pub currency_id: CurrencyId,
+	/// The total collateral amount held by CDP treasury.
+	pub total: Balance,
+	/// The collateral amount in collateral auctions.
+	pub in_auction: Balance,
+	/// The collateral amount in collateral auction lots waiting to start.
+	pub in_pending_lots: Balance,
+	/// The collateral amount reserved by split sells.
+	pub in_twap_sells: Balance,
+	/// The value of the total collateral in stable currency by the oracle
+	/// price, `None` if the oracle price is unavailable.
+	pub value: Option<Balance>,
+}
+
+/// The pools of a stable currency in CDP treasury.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct StableCurrencyHolding {
+	/// The stable currency.
+	pub currency_id: CurrencyId,
+	/// The surplus pool under the stable currency.
+	pub surplus_pool: Balance,
+	/// The debit pool under the stable currency.
+	pub debit_pool: Balance,
+}
+
+/// The balance sheet of CDP treasury at a block.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BalanceSheet<BlockNumber> {
+	/// The block at which the balance sheet is made.
+	pub block_number: BlockNumber,
+	/// The surplus pool of the default stable currency.
+	pub surplus_pool: Balance,
+	/// The debit pool of the default stable currency.
+	pub debit_pool: Balance,
+	/// The pools of all stable currencies, the default one comes first.
+	pub stable_currencies: Vec<StableCurrencyHolding>,
+	/// The insurance fund.
+	pub insurance_fund: Balance,
+	/// The total target stable amount of collateral auctions, including the
+	/// lots waiting to start.
+	pub total_target_in_auction: Balance,
+	/// The total stable amount to be raised by debit auctions.
+	pub total_debit_in_auction: Balance,
+	/// The holdings of collateral types.
+	pub collaterals: Vec<CollateralHolding>,
+	/// The total value of collaterals with oracle price in stable currency.
+	pub total_collateral_value: Balance,
+}
+
+/// A collateral sell split into multiple smaller swaps over several blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TwapSellOrder<BlockNumber> {
+	/// The collateral amount remaining to be sold.
+	pub remaining: Balance,
+	/// The block at which the next swap is executed.
+	pub next_swap_at: BlockNumber,
+}
+
+/// The CDP operations which can be paused by governance.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PausableOperation {
+	/// Issue debit or withdraw collateral by `setmint_core::adjust_loan`,
+	/// repayments and deposits are kept live.
+	AdjustLoan,
+	/// Close CDP by DEX with `setmint_core::close_loan_has_debit_by_dex`.
+	CloseLoanByDex,
+	/// Transfer CDP with `setmint_core::transfer_loan_from`.
+	TransferLoan,
+	/// Expand CDP with `setmint_core::expand_position_collateral`.
+	ExpandPositionCollateral,
+	/// Shrink CDP with `setmint_core::shrink_position_debit`.
+	ShrinkPositionDebit,
+	/// Liquidate unsafe CDP with `cdp_engine::liquidate`.
+	Liquidate,
+	/// Liquidate unsafe CDP by DEX, collateral auctions are created instead.
+	LiquidateByDex,
+	/// Swap collateral to stable currency by CDP treasury.
+	SwapCollateralToStable,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -49,6 +387,11 @@ pub mod module {
 		#[pallet::constant]
 		type GetSetUSDId: Get<CurrencyId>;
 
+		/// The stable currencies in which CDPs can issue debit, `GetSetUSDId`
+		/// is always supported
+		#[pallet::constant]
+		type StableCurrencyIds: Get<Vec<CurrencyId>>;
+
 		/// SERP Treasury for issuing/burning stable currency adjust standard value
 		/// adjustment
 		type SerpTreasury: SerpTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
@@ -56,10 +399,24 @@ pub mod module {
 		/// Auction manager creates auction to handle system surplus and debit
 		type AuctionManagerHandler: AuctionManager<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
+		/// Debit auction manager creates debit auction to recapitalise the
+		/// bad debt of system
+		type DebitAuctionManagerHandler: DebitAuctionManager;
+
+		/// Emergency shutdown.
+		type EmergencyShutdown: EmergencyShutdown;
+
 		/// Dex manager is used to swap confiscated collateral assets to stable
 		/// currency
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
+		/// The price source to limit the slippage of selling idle collateral
+		type PriceSource: PriceProvider<CurrencyId>;
+
+		/// The collateral types reported in the balance sheet of CDP treasury
+		#[pallet::constant]
+		type CollateralCurrencyIds: Get<Vec<CurrencyId>>;
+
 		/// The cap of lots number when create collateral auction on a
 		/// liquidation or to create debit/surplus auction on block end.
 		/// If set to 0, does not work.
@@ -75,11 +432,17 @@ pub mod module {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
-		/// The alternative swap path joint list, which can be concated to
-		/// alternative swap path when cdp treasury swap collateral to stable.
+		/// The default alternative swap path joint list, which can be
+		/// concated to alternative swap path when cdp treasury swap collateral
+		/// to stable, used by collateral types without a governed list.
 		#[pallet::constant]
 		type AlternativeSwapPathJointList: Get<Vec<Vec<CurrencyId>>>;
 
+		/// The capacity of the balance sheet snapshot ring, which bounds the
+		/// max count of snapshots kept.
+		#[pallet::constant]
+		type BalanceSheetSnapshotCapacity: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -92,10 +455,34 @@ pub mod module {
 		SurplusPoolNotEnough,
 		/// The debit pool of CDP treasury is not enough
 		DebitPoolNotEnough,
+		/// The currency id is not a supported stable currency
+		InvalidStableCurrencyType,
 		/// Cannot use collateral to swap stable
 		CannotSwap,
 		/// The currency id is not DexShare type
 		NotDexShare,
+		/// The debit auction params are invalid
+		InvalidDebitAuctionParams,
+		/// The collateral sell policy is invalid
+		InvalidCollateralSellPolicy,
+		/// The insurance fund params are invalid
+		InvalidInsuranceFundParams,
+		/// The insurance fund is not enough
+		InsuranceFundNotEnough,
+		/// The DEX price deviates from the oracle price more than the guard
+		ExceedPriceDeviation,
+		/// The oracle price is invalid
+		InvalidFeedPrice,
+		/// The swap price guard is invalid
+		InvalidSwapPriceGuard,
+		/// The lot split params are invalid
+		InvalidLotSplitParams,
+		/// The operation is paused
+		OperationPaused,
+		/// The automatic unpause block is not in the future
+		InvalidUnpauseBlock,
+		/// The max count of balance sheet snapshots exceeds the capacity
+		InvalidMaxBalanceSheetSnapshots,
 	}
 
 
@@ -108,6 +495,102 @@ pub mod module {
 			collateral_type: CurrencyId,
 			new_size: Balance,
 		},
+		/// The surplus buffer kept in CDP treasury updated.
+		SurplusBufferUpdated { new_buffer: Balance },
+		/// The cadence and cap of automatic surplus extraction updated.
+		SurplusExtractionParamsUpdated {
+			new_period: T::BlockNumber,
+			new_cap_per_period: Option<Balance>,
+		},
+		/// The excess surplus above the buffer has been forwarded to SERP.
+		SurplusExtractedToSerp { currency_id: CurrencyId, amount: Balance },
+		/// The debit auction params updated.
+		DebitAuctionParamsUpdated { new_params: Option<DebitAuctionParams> },
+		/// The sell policy of idle collateral of specific collateral type
+		/// updated.
+		CollateralSellPolicyUpdated {
+			collateral_type: CurrencyId,
+			new_policy: Option<CollateralSellPolicy>,
+		},
+		/// The period of selling idle collateral updated.
+		CollateralSellPeriodUpdated { new_period: T::BlockNumber },
+		/// Idle collateral has been swapped to stable currency by DEX.
+		IdleCollateralSwapped {
+			collateral_type: CurrencyId,
+			supply_collateral_amount: Balance,
+			target_stable_amount: Balance,
+		},
+		/// Idle collateral has been put into collateral auctions.
+		IdleCollateralAuctioned {
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+		},
+		/// The insurance fund params updated.
+		InsuranceFundParamsUpdated { new_params: InsuranceFundParams },
+		/// Surplus has been deposited into the insurance fund.
+		InsuranceFundDeposited { amount: Balance },
+		/// The insurance fund has been drawn to offset debit.
+		InsuranceFundDrawn { amount: Balance },
+		/// The insurance fund has been released to the surplus pool.
+		InsuranceFundReleased { amount: Balance },
+		/// The swap path joint list of specific collateral type updated.
+		SwapPathJointListUpdated {
+			collateral_type: CurrencyId,
+			new_list: Option<Vec<Vec<CurrencyId>>>,
+		},
+		/// The swap price guard of specific collateral type updated.
+		SwapPriceGuardUpdated {
+			collateral_type: CurrencyId,
+			new_guard: Option<SwapPriceGuard<T::BlockNumber>>,
+		},
+		/// A collateral sell exceeding the swap price guard has been split
+		/// into multiple swaps over several blocks.
+		TwapSellScheduled {
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+		},
+		/// A swap of a split collateral sell has been executed.
+		TwapSellExecuted {
+			collateral_type: CurrencyId,
+			supply_collateral_amount: Balance,
+			target_stable_amount: Balance,
+			remaining_collateral_amount: Balance,
+		},
+		/// A split collateral sell has been cancelled.
+		TwapSellCancelled {
+			collateral_type: CurrencyId,
+			remaining_collateral_amount: Balance,
+		},
+		/// The lot split params of specific collateral type updated.
+		LotSplitParamsUpdated {
+			collateral_type: CurrencyId,
+			new_params: LotSplitParams<T::BlockNumber>,
+		},
+		/// A collateral auction lot has been scheduled to start later.
+		CollateralAuctionLotScheduled {
+			collateral_type: CurrencyId,
+			amount: Balance,
+			target: Balance,
+			start_at: T::BlockNumber,
+		},
+		/// The params of balance sheet snapshots updated.
+		BalanceSheetSnapshotParamsUpdated {
+			new_period: T::BlockNumber,
+			new_max_snapshots: u32,
+		},
+		/// The operation paused for specific collateral type, or all
+		/// collateral types if `collateral_type` is `None`.
+		OperationPaused {
+			operation: PausableOperation,
+			collateral_type: Option<CurrencyId>,
+			unpause_at: Option<T::BlockNumber>,
+		},
+		/// The operation unpaused for specific collateral type, or all
+		/// collateral types if `collateral_type` is `None`.
+		OperationUnpaused {
+			operation: PausableOperation,
+			collateral_type: Option<CurrencyId>,
+		},
 	}
 
 	/// The expected amount size for per lot collateral auction of specific
@@ -121,10 +604,205 @@ pub mod module {
 	/// Current total debit value of system under `currency_id`. It's not same as debit in CDP
 	/// engine, it is the bad debt of the system under a specific stable currency.
 	///
-	/// DebitPool: map CurrencyId => Balance
+	/// DebitPools: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn debit_pool_of)]
+	pub type DebitPools<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The target amount of surplus kept in CDP treasury as reserve against
+	/// future bad debt, only the surplus above it can be extracted to SERP
+	/// automatically.
+	///
+	/// SurplusBuffer: value: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_buffer)]
+	pub type SurplusBuffer<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The period in blocks to extract the excess surplus to SERP
+	/// automatically. If set to 0, does not work.
+	///
+	/// SurplusExtractionPeriod: value: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_extraction_period)]
+	pub type SurplusExtractionPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The cap of surplus extracted to SERP automatically per period, `None`
+	/// means no cap.
+	///
+	/// SurplusExtractionCapPerPeriod: value: Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_extraction_cap_per_period)]
+	pub type SurplusExtractionCapPerPeriod<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The params of debit auctions, `None` means debit auctions are
+	/// disabled.
+	///
+	/// DebitAuctionSettings: value: Option<DebitAuctionParams>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_auction_params)]
+	pub type DebitAuctionSettings<T: Config> = StorageValue<_, DebitAuctionParams, OptionQuery>;
+
+	/// The sell policy of idle collateral under specific collateral type.
+	///
+	/// CollateralSellPolicies: map CurrencyId => Option<CollateralSellPolicy>
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_sell_policy)]
+	pub type CollateralSellPolicies<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CollateralSellPolicy, OptionQuery>;
+
+	/// The period in blocks to sell idle collateral automatically. If set to
+	/// 0, does not work.
+	///
+	/// CollateralSellPeriod: value: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_sell_period)]
+	pub type CollateralSellPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The bad debt and recovered stable currency under specific collateral
+	/// type.
+	///
+	/// BadDebtReports: map CurrencyId => BadDebtReport
+	#[pallet::storage]
+	#[pallet::getter(fn bad_debt_report)]
+	pub type BadDebtReports<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, BadDebtReport, ValueQuery>;
+
+	/// The params of the insurance fund.
+	///
+	/// InsuranceFundSettings: value: InsuranceFundParams
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_fund_params)]
+	pub type InsuranceFundSettings<T: Config> = StorageValue<_, InsuranceFundParams, ValueQuery>;
+
+	/// The surplus to be deposited into the insurance fund at block end.
+	///
+	/// PendingInsuranceContribution: value: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_insurance_contribution)]
+	pub type PendingInsuranceContribution<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The liquidation penalty not realised yet and the target of the
+	/// collateral auctions including it, under specific collateral type.
+	///
+	/// LiquidationPenaltiesInAuction: map CurrencyId => (Balance, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_penalties_in_auction)]
+	pub type LiquidationPenaltiesInAuction<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (Balance, Balance), ValueQuery>;
+
+	/// The alternative swap path joint list of specific collateral type,
+	/// falls back to `AlternativeSwapPathJointList` if not set.
+	///
+	/// SwapPathJointLists: map CurrencyId => Option<Vec<Vec<CurrencyId>>>
+	#[pallet::storage]
+	#[pallet::getter(fn swap_path_joint_list)]
+	pub type SwapPathJointLists<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Vec<Vec<CurrencyId>>, OptionQuery>;
+
+	/// The swap price guard of specific collateral type, `None` means the
+	/// DEX price is not checked against the oracle price.
+	///
+	/// SwapPriceGuards: map CurrencyId => Option<SwapPriceGuard>
+	#[pallet::storage]
+	#[pallet::getter(fn swap_price_guard)]
+	pub type SwapPriceGuards<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, SwapPriceGuard<T::BlockNumber>, OptionQuery>;
+
+	/// The split collateral sell of specific collateral type in progress.
+	///
+	/// TwapSellOrders: map CurrencyId => Option<TwapSellOrder>
+	#[pallet::storage]
+	#[pallet::getter(fn twap_sell_order)]
+	pub type TwapSellOrders<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, TwapSellOrder<T::BlockNumber>, OptionQuery>;
+
+	/// The lot split params of specific collateral type.
+	///
+	/// LotSplitSettings: map CurrencyId => LotSplitParams
+	#[pallet::storage]
+	#[pallet::getter(fn lot_split_params)]
+	pub type LotSplitSettings<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, LotSplitParams<T::BlockNumber>, ValueQuery>;
+
+	/// The collateral auction lots to be started at specific block.
+	///
+	/// PendingCollateralLots: map BlockNumber => Vec<PendingCollateralLot>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_collateral_lots)]
+	pub type PendingCollateralLots<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<PendingCollateralLot<T::AccountId>>, ValueQuery>;
+
+	/// The total collateral amount of specific collateral type in lots
+	/// waiting to start.
+	///
+	/// TotalCollateralInPendingLots: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_collateral_in_pending_lots)]
+	pub type TotalCollateralInPendingLots<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The total target stable amount of lots waiting to start.
+	///
+	/// TotalTargetInPendingLots: value: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_target_in_pending_lots)]
+	pub type TotalTargetInPendingLots<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The period in blocks to take a snapshot of the balance sheet. If set
+	/// to 0, does not work.
+	///
+	/// BalanceSheetSnapshotPeriod: value: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn balance_sheet_snapshot_period)]
+	pub type BalanceSheetSnapshotPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The max count of the latest balance sheet snapshots reported, bounded
+	/// by `BalanceSheetSnapshotCapacity`.
+	///
+	/// MaxBalanceSheetSnapshots: value: u32
+	#[pallet::storage]
+	#[pallet::getter(fn max_balance_sheet_snapshots)]
+	pub type MaxBalanceSheetSnapshots<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Ring of the historical snapshots of the balance sheet, indexed by
+	/// snapshot slot.
+	///
+	/// BalanceSheetSnapshots: map u32 => Option<BalanceSheet>
+	#[pallet::storage]
+	pub type BalanceSheetSnapshots<T: Config> =
+		StorageMap<_, Twox64Concat, u32, BalanceSheet<T::BlockNumber>, OptionQuery>;
+
+	/// The total count of balance sheet snapshots taken.
+	///
+	/// BalanceSheetSnapshotCount: value: u64
+	#[pallet::storage]
+	#[pallet::getter(fn balance_sheet_snapshot_count)]
+	pub type BalanceSheetSnapshotCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The paused operations, keyed by operation and collateral type (`None`
+	/// for all collateral types), with the block at which they are
+	/// automatically unpaused.
+	///
+	/// PausedOperations: double_map PausableOperation, Option<CurrencyId> =>
+	/// Option<Option<BlockNumber>>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_operation)]
+	pub type PausedOperations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PausableOperation,
+		Twox64Concat,
+		Option<CurrencyId>,
+		Option<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The paused operations to be automatically unpaused at the block.
+	///
+	/// ScheduledUnpauses: map BlockNumber => Vec<(PausableOperation,
+	/// Option<CurrencyId>)>
 	#[pallet::storage]
-	#[pallet::getter(fn debit_pool)]
-	pub type DebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
+	#[pallet::getter(fn scheduled_unpauses)]
+	pub type ScheduledUnpauses<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(PausableOperation, Option<CurrencyId>)>, ValueQuery>;
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
@@ -156,10 +834,57 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Extract the excess surplus above the buffer to SERP at the start
+		/// of every surplus extraction period, sell idle collateral at the
+		/// start of every collateral sell period, execute the due swaps of
+		/// split collateral sells, start the due staggered collateral auction
+		/// lots, create debit auctions when the uncovered bad debt exceeds
+		/// the trigger threshold, and take a snapshot of the balance sheet at
+		/// the start of every snapshot period.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut count: u32 = 0;
+			let period = Self::surplus_extraction_period();
+			if !period.is_zero() && (now % period).is_zero() {
+				Self::extract_excess_surplus();
+			}
+			let period = Self::collateral_sell_period();
+			if !period.is_zero() && (now % period).is_zero() {
+				count += Self::sell_idle_collaterals();
+			}
+			count += Self::execute_twap_sells(now);
+			count += Self::start_pending_collateral_lots(now);
+			count += Self::create_debit_auctions();
+			let period = Self::balance_sheet_snapshot_period();
+			if !period.is_zero() && (now % period).is_zero() {
+				count += Self::take_balance_sheet_snapshot(now);
+			}
+			count += Self::unpause_scheduled_operations(now);
+			T::WeightInfo::on_initialize(count)
+		}
+
+		/// Move the debit pool kept as a single value into the debit pool of
+		/// `GetSetUSDId`.
+		fn on_runtime_upgrade() -> Weight {
+			let pallet_name = <Pallet<T> as frame_support::traits::PalletInfoAccess>::name();
+			match frame_support::storage::migration::take_storage_value::<Balance>(
+				pallet_name.as_bytes(),
+				b"DebitPool",
+				&[],
+			) {
+				Some(debit) => {
+					DebitPools::<T>::mutate(T::GetSetUSDId::get(), |pool| *pool = pool.saturating_add(debit));
+					T::DbWeight::get().reads_writes(2, 2)
+				}
+				None => T::DbWeight::get().reads(1),
+			}
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: T::BlockNumber) {
-			// offset the same amount between debit pool and surplus pool
+			// offset the same amount between debit pool and insurance fund or surplus pool
 			Self::offset_surplus_and_debit();
+			// deposit the pending contribution from surplus pool to insurance fund
+			Self::contribute_to_insurance_fund();
 		}
 	}
 
@@ -208,7 +933,9 @@ pub mod module {
 			Ok(())
 		}
 
-		/// Swap the collateral not occupied by the auction to stable.
+		/// Swap the collateral not occupied by the auction to stable. If the
+		/// sell with exact supply exceeds the swap price guard, it is split
+		/// into multiple smaller swaps over several blocks.
 		///
 		/// The dispatch origin of this call must be `UpdateOrigin`.
 		///
@@ -222,6 +949,11 @@ pub mod module {
 			swap_limit: SwapLimit<Balance>,
 		) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
+			if let SwapLimit::ExactSupply(supply_amount, _) = swap_limit {
+				if Self::should_split_sell(currency_id, supply_amount) {
+					return Self::schedule_twap_sell(currency_id, supply_amount);
+				}
+			}
 			// the supply collateral must not be occupied by the auction.
 			Self::swap_collateral_to_stable(currency_id, swap_limit, false)?;
 			Ok(())
@@ -249,62 +981,1109 @@ pub mod module {
 			});
 			Ok(())
 		}
-	}
-}
-
-impl<T: Config> Pallet<T> {
-	/// Get account of cdp treasury module.
-	pub fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account()
-	}
-
-	/// Get current total surplus of system.
-	pub fn surplus_pool() -> Balance {
-		T::Currency::free_balance(T::GetSetUSDId::get(), &Self::account_id())
-	}
 
-	/// Get total collateral amount of cdp treasury module.
-	pub fn total_collaterals(currency_id: CurrencyId) -> Balance {
-		T::Currency::free_balance(currency_id, &Self::account_id())
-	}
-
-	/// Get collateral amount not in auction
-	pub fn total_collaterals_not_in_auction(currency_id: CurrencyId) -> Balance {
-		T::Currency::free_balance(currency_id, &Self::account_id())
-			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
-	}
+		/// Update the surplus buffer kept in CDP treasury.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `buffer`: target amount of surplus held against future bad debt
+		#[pallet::weight((T::WeightInfo::set_surplus_buffer(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_surplus_buffer(origin: OriginFor<T>, #[pallet::compact] buffer: Balance) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			SurplusBuffer::<T>::put(buffer);
+			Self::deposit_event(Event::SurplusBufferUpdated { new_buffer: buffer });
+			Ok(())
+		}
 
-	fn offset_surplus_and_debit() {
-		let offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::surplus_pool());
+		/// Update the cadence and cap of automatic surplus extraction.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `period`: extraction period in blocks, 0 disables the extraction
+		/// - `cap_per_period`: max surplus extracted per period, `None` means no cap
+		#[pallet::weight((T::WeightInfo::set_surplus_extraction_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_surplus_extraction_params(
+			origin: OriginFor<T>,
+			period: T::BlockNumber,
+			cap_per_period: Option<Balance>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			SurplusExtractionPeriod::<T>::put(period);
+			SurplusExtractionCapPerPeriod::<T>::set(cap_per_period);
+			Self::deposit_event(Event::SurplusExtractionParamsUpdated {
+				new_period: period,
+				new_cap_per_period: cap_per_period,
+			});
+			Ok(())
+		}
 
-		// Burn the amount that is equal to offset amount of stable currency.
-		if !offset_amount.is_zero() {
-			let res = T::Currency::withdraw(T::GetSetUSDId::get(), &Self::account_id(), offset_amount);
-			match res {
-				Ok(_) => {
-					DebitPool::<T>::mutate(|debit| {
-						*debit = debit
-							.checked_sub(offset_amount)
-							.expect("offset = min(debit, surplus); qed")
-					});
-				}
-				Err(e) => {
-					log::warn!(
-						target: "cdp-treasury",
-						"get_swap_supply_amount: Attempt to burn surplus {:?} failed: {:?}, this is unexpected but should be safe",
-						offset_amount, e
-					);
-				}
+		/// Update the params of debit auctions.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `params`: debit auction params, `None` disables debit auctions
+		#[pallet::weight((T::WeightInfo::set_debit_auction_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_debit_auction_params(origin: OriginFor<T>, params: Option<DebitAuctionParams>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(params) = &params {
+				ensure!(
+					!params.lot_size.is_zero() && !params.initial_issuance_per_lot.is_zero(),
+					Error::<T>::InvalidDebitAuctionParams,
+				);
 			}
+			DebitAuctionSettings::<T>::set(params.clone());
+			Self::deposit_event(Event::DebitAuctionParamsUpdated { new_params: params });
+			Ok(())
 		}
-	}
-}
-
-impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
-	type Balance = Balance;
-	type CurrencyId = CurrencyId;
 
-	fn get_surplus_pool() -> Self::Balance {
+		/// Update the sell policy of idle collateral under specific
+		/// collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `policy`: sell policy, `None` disables selling of this collateral
+		#[pallet::weight((T::WeightInfo::set_collateral_sell_policy(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_collateral_sell_policy(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			policy: Option<CollateralSellPolicy>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(policy) = &policy {
+				ensure!(
+					policy.max_swap_slippage <= Ratio::one(),
+					Error::<T>::InvalidCollateralSellPolicy,
+				);
+			}
+			CollateralSellPolicies::<T>::set(currency_id, policy.clone());
+			Self::deposit_event(Event::CollateralSellPolicyUpdated {
+				collateral_type: currency_id,
+				new_policy: policy,
+			});
+			Ok(())
+		}
+
+		/// Update the params of the insurance fund.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `params`: shares of liquidation penalties and surplus contributed
+		///   to the insurance fund, and the cap of it
+		#[pallet::weight((T::WeightInfo::set_insurance_fund_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_insurance_fund_params(origin: OriginFor<T>, params: InsuranceFundParams) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				params.penalty_share <= Ratio::one() && params.surplus_share <= Ratio::one(),
+				Error::<T>::InvalidInsuranceFundParams,
+			);
+			InsuranceFundSettings::<T>::put(params.clone());
+			Self::deposit_event(Event::InsuranceFundParamsUpdated { new_params: params });
+			Ok(())
+		}
+
+		/// Release the insurance fund to the surplus pool.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `amount`: amount of stable currency released
+		#[pallet::weight(T::WeightInfo::release_insurance_fund())]
+		#[transactional]
+		pub fn release_insurance_fund(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::insurance_fund() >= amount, Error::<T>::InsuranceFundNotEnough);
+			T::Currency::transfer(
+				T::GetSetUSDId::get(),
+				&Self::insurance_fund_account_id(),
+				&Self::account_id(),
+				amount,
+			)?;
+			Self::deposit_event(Event::InsuranceFundReleased { amount });
+			Ok(())
+		}
+
+		/// Update the period of selling idle collateral.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `period`: sell period in blocks, 0 disables the selling
+		#[pallet::weight((T::WeightInfo::set_collateral_sell_period(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_collateral_sell_period(origin: OriginFor<T>, period: T::BlockNumber) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			CollateralSellPeriod::<T>::put(period);
+			Self::deposit_event(Event::CollateralSellPeriodUpdated { new_period: period });
+			Ok(())
+		}
+
+		/// Update the params of splitting collateral auctions under specific
+		/// collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `params`: lot split strategy and stagger interval of lot starts
+		#[pallet::weight((T::WeightInfo::set_lot_split_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_lot_split_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: LotSplitParams<T::BlockNumber>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let valid = match params.strategy {
+				LotSplitStrategy::FixedSize => true,
+				LotSplitStrategy::ValueBased { lot_value } => !lot_value.is_zero(),
+				LotSplitStrategy::Geometric { ratio } => !ratio.is_zero(),
+			};
+			ensure!(valid, Error::<T>::InvalidLotSplitParams);
+			LotSplitSettings::<T>::insert(currency_id, params.clone());
+			Self::deposit_event(Event::LotSplitParamsUpdated {
+				collateral_type: currency_id,
+				new_params: params,
+			});
+			Ok(())
+		}
+
+		/// Update the params of balance sheet snapshots.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `period`: snapshot period in blocks, 0 disables the snapshots
+		/// - `max_snapshots`: max count of snapshots kept, no more than
+		///   `BalanceSheetSnapshotCapacity`
+		#[pallet::weight((T::WeightInfo::set_balance_sheet_snapshot_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_balance_sheet_snapshot_params(
+			origin: OriginFor<T>,
+			period: T::BlockNumber,
+			max_snapshots: u32,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				max_snapshots <= T::BalanceSheetSnapshotCapacity::get(),
+				Error::<T>::InvalidMaxBalanceSheetSnapshots,
+			);
+			BalanceSheetSnapshotPeriod::<T>::put(period);
+			MaxBalanceSheetSnapshots::<T>::put(max_snapshots);
+			Self::deposit_event(Event::BalanceSheetSnapshotParamsUpdated {
+				new_period: period,
+				new_max_snapshots: max_snapshots,
+			});
+			Ok(())
+		}
+
+		/// Pause the operation for specific collateral type or all collateral
+		/// types.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `operation`: the operation to pause
+		/// - `currency_id`: collateral type, `None` means all collateral types
+		/// - `unpause_at`: the block at which the operation is automatically
+		///   unpaused, `None` means it's paused until `unpause_operation`
+		#[pallet::weight((T::WeightInfo::pause_operation(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn pause_operation(
+			origin: OriginFor<T>,
+			operation: PausableOperation,
+			currency_id: Option<CurrencyId>,
+			unpause_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(block_number) = unpause_at {
+				ensure!(
+					block_number > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::InvalidUnpauseBlock
+				);
+			}
+
+			// replace the previous schedule of the operation, if any
+			Self::cancel_scheduled_unpause(operation, currency_id);
+			if let Some(block_number) = unpause_at {
+				ScheduledUnpauses::<T>::append(block_number, (operation, currency_id));
+			}
+			PausedOperations::<T>::insert(operation, currency_id, unpause_at);
+			Self::deposit_event(Event::OperationPaused {
+				operation,
+				collateral_type: currency_id,
+				unpause_at,
+			});
+			Ok(())
+		}
+
+		/// Unpause the operation for specific collateral type or all collateral
+		/// types.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `operation`: the operation to unpause
+		/// - `currency_id`: collateral type, `None` means all collateral types
+		#[pallet::weight((T::WeightInfo::unpause_operation(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn unpause_operation(
+			origin: OriginFor<T>,
+			operation: PausableOperation,
+			currency_id: Option<CurrencyId>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::cancel_scheduled_unpause(operation, currency_id);
+			if PausedOperations::<T>::take(operation, currency_id).is_some() {
+				Self::deposit_event(Event::OperationUnpaused {
+					operation,
+					collateral_type: currency_id,
+				});
+			}
+			Ok(())
+		}
+
+		/// Update the alternative swap path joint list under specific
+		/// collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `list`: swap path joint list, `None` falls back to the default list
+		#[pallet::weight((T::WeightInfo::set_swap_path_joint_list(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_swap_path_joint_list(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			list: Option<Vec<Vec<CurrencyId>>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			SwapPathJointLists::<T>::set(currency_id, list.clone());
+			Self::deposit_event(Event::SwapPathJointListUpdated {
+				collateral_type: currency_id,
+				new_list: list,
+			});
+			Ok(())
+		}
+
+		/// Update the swap price guard under specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `guard`: swap price guard, `None` disables the guard
+		#[pallet::weight((T::WeightInfo::set_swap_price_guard(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_swap_price_guard(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			guard: Option<SwapPriceGuard<T::BlockNumber>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(guard) = &guard {
+				ensure!(guard.max_deviation <= Ratio::one(), Error::<T>::InvalidSwapPriceGuard);
+			}
+			SwapPriceGuards::<T>::set(currency_id, guard.clone());
+			Self::deposit_event(Event::SwapPriceGuardUpdated {
+				collateral_type: currency_id,
+				new_guard: guard,
+			});
+			Ok(())
+		}
+
+		/// Cancel the split collateral sell under specific collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		#[pallet::weight(T::WeightInfo::cancel_twap_sell())]
+		#[transactional]
+		pub fn cancel_twap_sell(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(order) = TwapSellOrders::<T>::take(currency_id) {
+				Self::deposit_event(Event::TwapSellCancelled {
+					collateral_type: currency_id,
+					remaining_collateral_amount: order.remaining,
+				});
+			}
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get account of cdp treasury module.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Get account of the insurance fund.
+	pub fn insurance_fund_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account(b"insurance")
+	}
+
+	/// Get current total surplus of system.
+	pub fn surplus_pool() -> Balance {
+		Self::surplus_pool_of(T::GetSetUSDId::get())
+	}
+
+	/// Get current total surplus of system under `stable_currency_id`.
+	pub fn surplus_pool_of(stable_currency_id: CurrencyId) -> Balance {
+		T::Currency::free_balance(stable_currency_id, &Self::account_id())
+	}
+
+	/// Get current total debit of system.
+	pub fn debit_pool() -> Balance {
+		Self::debit_pool_of(T::GetSetUSDId::get())
+	}
+
+	/// Get all stable currencies in which CDPs can issue debit, starting
+	/// with `GetSetUSDId`.
+	pub fn stable_currency_ids() -> Vec<CurrencyId> {
+		let default_stable_currency_id = T::GetSetUSDId::get();
+		let mut stable_currency_ids = vec![default_stable_currency_id];
+		stable_currency_ids.extend(
+			T::StableCurrencyIds::get()
+				.into_iter()
+				.filter(|currency_id| *currency_id != default_stable_currency_id),
+		);
+		stable_currency_ids
+	}
+
+	fn ensure_stable_currency(stable_currency_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			stable_currency_id == T::GetSetUSDId::get() || T::StableCurrencyIds::get().contains(&stable_currency_id),
+			Error::<T>::InvalidStableCurrencyType,
+		);
+		Ok(())
+	}
+
+	/// Get current insurance fund of system.
+	pub fn insurance_fund() -> Balance {
+		T::Currency::free_balance(T::GetSetUSDId::get(), &Self::insurance_fund_account_id())
+	}
+
+	/// Get total collateral amount of cdp treasury module.
+	pub fn total_collaterals(currency_id: CurrencyId) -> Balance {
+		T::Currency::free_balance(currency_id, &Self::account_id())
+	}
+
+	/// Get collateral amount neither in auction nor in lots waiting to start
+	pub fn total_collaterals_not_in_auction(currency_id: CurrencyId) -> Balance {
+		T::Currency::free_balance(currency_id, &Self::account_id())
+			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
+			.saturating_sub(Self::total_collateral_in_pending_lots(currency_id))
+	}
+
+	/// Split `amount` of collateral with `target` into collateral auction
+	/// lots of `(amount, target)` according to the lot split params. The sum
+	/// of lot amounts and targets always equal to `amount` and `target`.
+	pub fn split_collateral_auction_lots(
+		currency_id: CurrencyId,
+		amount: Balance,
+		target: Balance,
+		splited: bool,
+	) -> Vec<(Balance, Balance)> {
+		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
+		let expected_collateral_auction_size = Self::expected_collateral_auction_size(currency_id);
+		let lot_amounts = if !splited || max_auctions_count.is_zero() {
+			vec![amount]
+		} else {
+			match Self::lot_split_params(currency_id).strategy {
+				LotSplitStrategy::FixedSize => {
+					Self::equal_lot_amounts(amount, expected_collateral_auction_size, max_auctions_count)
+				}
+				LotSplitStrategy::ValueBased { lot_value } => {
+					let lot_size = T::PriceSource::get_relative_price(currency_id, T::GetSetUSDId::get())
+						.and_then(|price| price.reciprocal())
+						.map(|reciprocal| reciprocal.saturating_mul_int(lot_value))
+						.unwrap_or(expected_collateral_auction_size);
+					Self::equal_lot_amounts(amount, lot_size, max_auctions_count)
+				}
+				LotSplitStrategy::Geometric { ratio } => Self::geometric_lot_amounts(
+					amount,
+					expected_collateral_auction_size,
+					ratio,
+					max_auctions_count,
+				),
+			}
+		};
+
+		// split target in proportion to lot amounts, the last lot takes the
+		// rounding remnant
+		let lots_count = lot_amounts.len();
+		let mut unhandled_target = target;
+		lot_amounts
+			.into_iter()
+			.enumerate()
+			.map(|(index, lot_amount)| {
+				let lot_target = if index + 1 == lots_count {
+					unhandled_target
+				} else {
+					Ratio::checked_from_rational(lot_amount, amount)
+						.map(|proportion| proportion.saturating_mul_int(target))
+						.unwrap_or_default()
+						.min(unhandled_target)
+				};
+				unhandled_target = unhandled_target.saturating_sub(lot_target);
+				(lot_amount, lot_target)
+			})
+			.collect()
+	}
+
+	/// Split `amount` into lots of equal amount of about `lot_size`, the last
+	/// lot takes the remnant.
+	fn equal_lot_amounts(amount: Balance, lot_size: Balance, max_lots_count: Balance) -> Vec<Balance> {
+		if lot_size.is_zero() || amount <= lot_size {
+			return vec![amount];
+		}
+
+		let mut lots_count = amount.checked_div(lot_size).expect("lot size is not zero; qed");
+		let remainder = amount.checked_rem(lot_size).expect("lot size is not zero; qed");
+		if !remainder.is_zero() {
+			lots_count = lots_count.saturating_add(One::one());
+		}
+		let lots_count = sp_std::cmp::min(lots_count, max_lots_count);
+		let average_amount_per_lot = amount.checked_div(lots_count).expect("lots count is at least 1; qed");
+
+		let mut lot_amounts = vec![average_amount_per_lot; (lots_count - 1) as usize];
+		lot_amounts.push(amount.saturating_sub(average_amount_per_lot.saturating_mul(lots_count - 1)));
+		lot_amounts
+	}
+
+	/// Split `amount` into lots growing by `ratio` from `first_lot_size`, the
+	/// last lot takes the remnant.
+	fn geometric_lot_amounts(
+		amount: Balance,
+		first_lot_size: Balance,
+		ratio: Ratio,
+		max_lots_count: Balance,
+	) -> Vec<Balance> {
+		let mut lot_amounts = vec![];
+		let mut unhandled_amount = amount;
+		let mut lot_size = first_lot_size;
+		while !lot_size.is_zero()
+			&& unhandled_amount > lot_size
+			&& (lot_amounts.len() as Balance).saturating_add(One::one()) < max_lots_count
+		{
+			lot_amounts.push(lot_size);
+			unhandled_amount = unhandled_amount.saturating_sub(lot_size);
+			lot_size = ratio.saturating_mul_int(lot_size).max(One::one());
+		}
+		lot_amounts.push(unhandled_amount);
+		lot_amounts
+	}
+
+	/// Start the collateral auction lots scheduled at `now`, or all lots
+	/// waiting to start after emergency shutdown, return the count of started
+	/// lots.
+	fn start_pending_collateral_lots(now: T::BlockNumber) -> u32 {
+		let lots: Vec<PendingCollateralLot<T::AccountId>> = if T::EmergencyShutdown::is_shutdown() {
+			PendingCollateralLots::<T>::drain().flat_map(|(_, lots)| lots).collect()
+		} else {
+			PendingCollateralLots::<T>::take(now)
+		};
+
+		let mut count: u32 = 0;
+		for lot in lots {
+			count += 1;
+			TotalCollateralInPendingLots::<T>::mutate(lot.currency_id, |total| {
+				*total = total.saturating_sub(lot.amount)
+			});
+			TotalTargetInPendingLots::<T>::mutate(|total| *total = total.saturating_sub(lot.target));
+			if let Err(e) = T::AuctionManagerHandler::new_collateral_auction(
+				&lot.refund_receiver,
+				lot.currency_id,
+				lot.amount,
+				lot.target,
+			) {
+				log::warn!(
+					target: "cdp-treasury",
+					"start_pending_collateral_lots: Attempt to start collateral auction of {:?} {:?} failed: {:?}",
+					lot.amount, lot.currency_id, e
+				);
+			}
+		}
+		count
+	}
+
+	/// Get the total target stable amount of collateral auctions, including
+	/// the lots waiting to start.
+	pub fn total_target_in_auction() -> Balance {
+		T::AuctionManagerHandler::get_total_target_in_auction().saturating_add(Self::total_target_in_pending_lots())
+	}
+
+	/// Get collateral amount neither in auction nor reserved by split sells.
+	pub fn idle_collaterals(currency_id: CurrencyId) -> Balance {
+		let reserved = Self::twap_sell_order(currency_id)
+			.map(|order| order.remaining)
+			.unwrap_or_default();
+		Self::total_collaterals_not_in_auction(currency_id).saturating_sub(reserved)
+	}
+
+	/// Get the alternative swap path joint list of specific collateral type.
+	pub fn swap_path_joints(currency_id: CurrencyId) -> Vec<Vec<CurrencyId>> {
+		Self::swap_path_joint_list(currency_id).unwrap_or_else(T::AlternativeSwapPathJointList::get)
+	}
+
+	/// Ensure the DEX price of swapping `currency_id` to `stable_currency_id`
+	/// by `swap_path` does not deviate from the oracle price more than the
+	/// swap price guard.
+	fn ensure_within_price_guard(
+		currency_id: CurrencyId,
+		stable_currency_id: CurrencyId,
+		swap_path: &[CurrencyId],
+		limit: SwapLimit<Balance>,
+	) -> DispatchResult {
+		if let Some(guard) = Self::swap_price_guard(currency_id) {
+			let (supply_amount, target_amount) =
+				T::DEX::get_swap_amount(swap_path, limit).ok_or(Error::<T>::CannotSwap)?;
+			let price = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			let min_target_amount = Ratio::one()
+				.saturating_sub(guard.max_deviation)
+				.saturating_mul_int(price.saturating_mul_int(supply_amount));
+			ensure!(target_amount >= min_target_amount, Error::<T>::ExceedPriceDeviation);
+		}
+		Ok(())
+	}
+
+	/// Whether swapping `amount` of `currency_id` at once exceeds the swap
+	/// price guard and can be split into multiple swaps.
+	fn should_split_sell(currency_id: CurrencyId, amount: Balance) -> bool {
+		let guard = match Self::swap_price_guard(currency_id) {
+			Some(guard) => guard,
+			None => return false,
+		};
+		if guard.twap_chunk_size.is_zero() || amount <= guard.twap_chunk_size {
+			return false;
+		}
+
+		let limit = SwapLimit::ExactSupply(amount, Zero::zero());
+		match T::DEX::get_best_price_swap_path(
+			currency_id,
+			T::GetSetUSDId::get(),
+			limit,
+			Self::swap_path_joints(currency_id),
+		) {
+			Some(swap_path) => {
+				Self::ensure_within_price_guard(currency_id, T::GetSetUSDId::get(), &swap_path, limit).is_err()
+			}
+			None => false,
+		}
+	}
+
+	fn schedule_twap_sell(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		ensure!(
+			Self::idle_collaterals(currency_id) >= amount,
+			Error::<T>::CollateralNotEnough,
+		);
+		let now = <frame_system::Pallet<T>>::block_number();
+		TwapSellOrders::<T>::mutate(currency_id, |maybe_order| {
+			let order = maybe_order.get_or_insert(TwapSellOrder {
+				remaining: Zero::zero(),
+				next_swap_at: now,
+			});
+			order.remaining = order.remaining.saturating_add(amount);
+		});
+		Self::deposit_event(Event::TwapSellScheduled {
+			collateral_type: currency_id,
+			collateral_amount: amount,
+		});
+		Ok(())
+	}
+
+	/// Get the min stable amount accepted for swapping `amount` of
+	/// `currency_id` in a split sell, which is the oracle value of `amount`
+	/// less the max deviation of the swap price guard. Returns `None` without
+	/// the guard or the oracle price.
+	fn twap_slice_min_target(currency_id: CurrencyId, amount: Balance) -> Option<Balance> {
+		let guard = Self::swap_price_guard(currency_id)?;
+		let price = T::PriceSource::get_relative_price(currency_id, T::GetSetUSDId::get())?;
+		Some(
+			Ratio::one()
+				.saturating_sub(guard.max_deviation)
+				.saturating_mul_int(price.saturating_mul_int(amount)),
+		)
+	}
+
+	/// Execute the due swaps of split collateral sells, return the count of
+	/// handled sells.
+	fn execute_twap_sells(now: T::BlockNumber) -> u32 {
+		let mut count: u32 = 0;
+		let orders: Vec<(CurrencyId, TwapSellOrder<T::BlockNumber>)> = TwapSellOrders::<T>::iter()
+			.filter(|(_, order)| order.next_swap_at <= now)
+			.collect();
+
+		for (currency_id, mut order) in orders {
+			count += 1;
+			let (chunk_size, interval) = match Self::swap_price_guard(currency_id) {
+				Some(guard) if !guard.twap_chunk_size.is_zero() => (guard.twap_chunk_size, guard.twap_interval),
+				_ => (order.remaining, Zero::zero()),
+			};
+			let amount = order
+				.remaining
+				.min(chunk_size)
+				.min(Self::total_collaterals_not_in_auction(currency_id));
+			if amount.is_zero() {
+				TwapSellOrders::<T>::remove(currency_id);
+				continue;
+			}
+
+			let swap_result = Self::twap_slice_min_target(currency_id, amount)
+				.ok_or_else(|| DispatchError::from(Error::<T>::InvalidFeedPrice))
+				.and_then(|min_target_amount| {
+					Self::swap_idle_collateral_to_stable(currency_id, SwapLimit::ExactSupply(amount, min_target_amount))
+				});
+			match swap_result {
+				Ok((supply_amount, target_amount)) => {
+					order.remaining = order.remaining.saturating_sub(supply_amount);
+					Self::deposit_event(Event::TwapSellExecuted {
+						collateral_type: currency_id,
+						supply_collateral_amount: supply_amount,
+						target_stable_amount: target_amount,
+						remaining_collateral_amount: order.remaining,
+					});
+				}
+				Err(e) => {
+					log::warn!(
+						target: "cdp-treasury",
+						"execute_twap_sells: Attempt to swap {:?} {:?} failed: {:?}",
+						amount, currency_id, e
+					);
+				}
+			}
+
+			if order.remaining.is_zero() {
+				TwapSellOrders::<T>::remove(currency_id);
+			} else {
+				order.next_swap_at = now.saturating_add(interval.max(One::one()));
+				TwapSellOrders::<T>::insert(currency_id, order);
+			}
+		}
+		count
+	}
+
+	/// Unpause the operations whose automatic unpause block is `now`, return
+	/// the count of unpaused operations.
+	fn unpause_scheduled_operations(now: T::BlockNumber) -> u32 {
+		let mut count: u32 = 0;
+		for (operation, currency_id) in ScheduledUnpauses::<T>::take(now) {
+			if Self::paused_operation(operation, currency_id) == Some(Some(now)) {
+				PausedOperations::<T>::remove(operation, currency_id);
+				Self::deposit_event(Event::OperationUnpaused {
+					operation,
+					collateral_type: currency_id,
+				});
+				count += 1;
+			}
+		}
+		count
+	}
+
+	/// Remove the scheduled automatic unpause of the paused operation, if
+	/// any.
+	fn cancel_scheduled_unpause(operation: PausableOperation, currency_id: Option<CurrencyId>) {
+		if let Some(Some(block_number)) = Self::paused_operation(operation, currency_id) {
+			ScheduledUnpauses::<T>::mutate_exists(block_number, |maybe_scheduled| {
+				if let Some(scheduled) = maybe_scheduled {
+					scheduled.retain(|item| *item != (operation, currency_id));
+					if scheduled.is_empty() {
+						*maybe_scheduled = None;
+					}
+				}
+			});
+		}
+	}
+
+	/// Check whether `operation` is paused for `currency_id`.
+	pub fn is_operation_paused(operation: PausableOperation, currency_id: CurrencyId) -> bool {
+		PausedOperations::<T>::contains_key(operation, None::<CurrencyId>)
+			|| PausedOperations::<T>::contains_key(operation, Some(currency_id))
+	}
+
+	/// Get the balance sheet of CDP treasury with oracle valuations of
+	/// collaterals.
+	pub fn balance_sheet() -> BalanceSheet<T::BlockNumber> {
+		let mut total_collateral_value: Balance = Zero::zero();
+		let collaterals = T::CollateralCurrencyIds::get()
+			.into_iter()
+			.map(|currency_id| {
+				let total = Self::total_collaterals(currency_id);
+				let value = T::PriceSource::get_relative_price(currency_id, T::GetSetUSDId::get())
+					.map(|price| price.saturating_mul_int(total));
+				total_collateral_value = total_collateral_value.saturating_add(value.unwrap_or_default());
+				CollateralHolding {
+					currency_id,
+					total,
+					in_auction: T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id),
+					in_pending_lots: Self::total_collateral_in_pending_lots(currency_id),
+					in_twap_sells: Self::twap_sell_order(currency_id)
+						.map(|order| order.remaining)
+						.unwrap_or_default(),
+					value,
+				}
+			})
+			.collect();
+		let stable_currencies = Self::stable_currency_ids()
+			.into_iter()
+			.map(|currency_id| StableCurrencyHolding {
+				currency_id,
+				surplus_pool: Self::surplus_pool_of(currency_id),
+				debit_pool: Self::debit_pool_of(currency_id),
+			})
+			.collect();
+
+		BalanceSheet {
+			block_number: <frame_system::Pallet<T>>::block_number(),
+			surplus_pool: Self::surplus_pool(),
+			debit_pool: Self::debit_pool(),
+			stable_currencies,
+			insurance_fund: Self::insurance_fund(),
+			total_target_in_auction: Self::total_target_in_auction(),
+			total_debit_in_auction: T::DebitAuctionManagerHandler::get_total_debit_in_auction(),
+			collaterals,
+			total_collateral_value,
+		}
+	}
+
+	/// Take a snapshot of the balance sheet, return the count of reported
+	/// collateral types.
+	fn take_balance_sheet_snapshot(now: T::BlockNumber) -> u32 {
+		let capacity = u64::from(T::BalanceSheetSnapshotCapacity::get());
+		if capacity.is_zero() || Self::max_balance_sheet_snapshots().is_zero() {
+			return 0;
+		}
+
+		let balance_sheet = BalanceSheet {
+			block_number: now,
+			..Self::balance_sheet()
+		};
+		let collaterals_count = balance_sheet.collaterals.len() as u32;
+		let count = Self::balance_sheet_snapshot_count();
+		BalanceSheetSnapshots::<T>::insert((count % capacity) as u32, balance_sheet);
+		BalanceSheetSnapshotCount::<T>::put(count.saturating_add(1));
+		collaterals_count
+	}
+
+	/// Get the latest `MaxBalanceSheetSnapshots` snapshots of the balance
+	/// sheet, from the oldest to the latest.
+	pub fn balance_sheet_snapshots() -> Vec<BalanceSheet<T::BlockNumber>> {
+		let capacity = u64::from(T::BalanceSheetSnapshotCapacity::get());
+		let max_snapshots = u64::from(Self::max_balance_sheet_snapshots()).min(capacity);
+		if max_snapshots.is_zero() {
+			return Vec::new();
+		}
+		let count = Self::balance_sheet_snapshot_count();
+		(count.saturating_sub(max_snapshots)..count)
+			.filter_map(|index| BalanceSheetSnapshots::<T>::get((index % capacity) as u32))
+			.collect()
+	}
+
+	/// Get the bad debt reports of all collateral types.
+	pub fn bad_debt_reports() -> Vec<(CurrencyId, BadDebtReport)> {
+		BadDebtReports::<T>::iter().collect()
+	}
+
+	/// Get the value of `amount` of `stable_currency_id` in `GetSetUSDId` at
+	/// the price of oracle, `None` if the price is unavailable.
+	fn default_stable_value_of(stable_currency_id: CurrencyId, amount: Balance) -> Option<Balance> {
+		if stable_currency_id == T::GetSetUSDId::get() {
+			Some(amount)
+		} else {
+			T::PriceSource::get_relative_price(stable_currency_id, T::GetSetUSDId::get())
+				.map(|price| price.saturating_mul_int(amount))
+		}
+	}
+
+	/// Get the surplus of `GetSetUSDId` which can be extracted to SERP.
+	pub fn excess_surplus() -> Balance {
+		Self::excess_surplus_of(T::GetSetUSDId::get())
+	}
+
+	/// Get the surplus under `stable_currency_id` above its debit pool which
+	/// can be extracted to SERP. The surplus buffer and the pending insurance
+	/// contribution are only kept in `GetSetUSDId`.
+	pub fn excess_surplus_of(stable_currency_id: CurrencyId) -> Balance {
+		let excess_surplus =
+			Self::surplus_pool_of(stable_currency_id).saturating_sub(Self::debit_pool_of(stable_currency_id));
+		if stable_currency_id == T::GetSetUSDId::get() {
+			excess_surplus
+				.saturating_sub(Self::surplus_buffer())
+				.saturating_sub(Self::pending_insurance_contribution())
+		} else {
+			excess_surplus
+		}
+	}
+
+	/// Extract the excess surplus of every stable currency to SERP, each
+	/// capped by the cap per period.
+	fn extract_excess_surplus() {
+		for stable_currency_id in Self::stable_currency_ids() {
+			let excess_surplus = Self::excess_surplus_of(stable_currency_id);
+			let amount = match Self::surplus_extraction_cap_per_period() {
+				Some(cap) => sp_std::cmp::min(excess_surplus, cap),
+				None => excess_surplus,
+			};
+
+			if !amount.is_zero() {
+				match Self::do_extract_surplus_to_serp(stable_currency_id, amount) {
+					Ok(_) => {
+						Self::deposit_event(Event::SurplusExtractedToSerp {
+							currency_id: stable_currency_id,
+							amount,
+						});
+					}
+					Err(e) => {
+						log::warn!(
+							target: "cdp-treasury",
+							"extract_excess_surplus: Attempt to extract surplus {:?} {:?} to serp failed: {:?}",
+							amount, stable_currency_id, e
+						);
+					}
+				}
+			}
+		}
+	}
+
+	#[transactional]
+	fn do_extract_surplus_to_serp(stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		T::SerpTreasury::on_serplus(stable_currency_id, amount)
+	}
+
+	/// Sell idle collateral according to the sell policies, return the count
+	/// of handled collateral types.
+	fn sell_idle_collaterals() -> u32 {
+		let mut count: u32 = 0;
+		for (currency_id, policy) in CollateralSellPolicies::<T>::iter() {
+			count += 1;
+			let idle_amount = Self::idle_collaterals(currency_id);
+			let sell_amount = sp_std::cmp::min(idle_amount, policy.max_sell_amount_per_period);
+			if sell_amount.is_zero() || idle_amount < policy.threshold {
+				continue;
+			}
+
+			if let Err(e) = Self::sell_idle_collateral(currency_id, sell_amount, policy.max_swap_slippage) {
+				log::warn!(
+					target: "cdp-treasury",
+					"sell_idle_collaterals: Attempt to sell {:?} {:?} failed: {:?}",
+					sell_amount, currency_id, e
+				);
+			}
+		}
+		count
+	}
+
+	/// Swap idle collateral to stable currency by DEX if the price is within
+	/// the oracle slippage, otherwise create collateral auctions for it.
+	#[transactional]
+	fn sell_idle_collateral(currency_id: CurrencyId, amount: Balance, max_swap_slippage: Ratio) -> DispatchResult {
+		if let Some(price) = T::PriceSource::get_relative_price(currency_id, T::GetSetUSDId::get()) {
+			let min_target_amount = Ratio::one()
+				.saturating_sub(max_swap_slippage)
+				.saturating_mul_int(price.saturating_mul_int(amount));
+
+			if let Ok((supply_amount, target_amount)) =
+				Self::swap_idle_collateral_to_stable(currency_id, SwapLimit::ExactSupply(amount, min_target_amount))
+			{
+				Self::deposit_event(Event::IdleCollateralSwapped {
+					collateral_type: currency_id,
+					supply_collateral_amount: supply_amount,
+					target_stable_amount: target_amount,
+				});
+				return Ok(());
+			}
+		}
+
+		<Self as CDPTreasuryExtended<T::AccountId>>::create_collateral_auctions(
+			currency_id,
+			amount,
+			Zero::zero(),
+			Self::account_id(),
+			true,
+		)?;
+		Self::deposit_event(Event::IdleCollateralAuctioned {
+			collateral_type: currency_id,
+			collateral_amount: amount,
+		});
+		Ok(())
+	}
+
+	#[transactional]
+	fn swap_idle_collateral_to_stable(
+		currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		Self::swap_collateral_to_stable(currency_id, limit, false)
+	}
+
+	/// Get the debit of `GetSetUSDId` which is neither offset by surplus nor
+	/// being raised by debit auctions. Debit auctions raise `GetSetUSDId`
+	/// only, the debit of other stable currencies is offset by their own
+	/// surplus, and is moved into `GetSetUSDId` when the collateral covering
+	/// it is auctioned.
+	pub fn uncovered_debit() -> Balance {
+		Self::debit_pool()
+			.saturating_sub(Self::surplus_pool())
+			.saturating_sub(T::DebitAuctionManagerHandler::get_total_debit_in_auction())
+	}
+
+	/// Create debit auctions for the uncovered debit, return the count of
+	/// created auctions.
+	fn create_debit_auctions() -> u32 {
+		let params = match Self::debit_auction_params() {
+			Some(params) => params,
+			None => return 0,
+		};
+		let uncovered_debit = Self::uncovered_debit();
+		if uncovered_debit <= params.trigger_threshold {
+			return 0;
+		}
+
+		let mut unhandled_debit = uncovered_debit;
+		let mut remaining_issuance = params.max_issuance.saturating_sub(
+			T::DebitAuctionManagerHandler::get_total_issuance_in_auction(params.issuance_currency_id),
+		);
+		let mut created_count: u32 = 0;
+
+		while !unhandled_debit.is_zero() && created_count < T::MaxAuctionsCount::get() {
+			let fix = sp_std::cmp::min(params.lot_size, unhandled_debit);
+			// the last lot may be less than lot size, issue proportionally
+			let initial_amount = Ratio::checked_from_rational(fix, params.lot_size)
+				.and_then(|n| n.checked_mul_int(params.initial_issuance_per_lot))
+				.unwrap_or(params.initial_issuance_per_lot);
+			if initial_amount.is_zero() || initial_amount > remaining_issuance {
+				break;
+			}
+
+			if let Err(e) =
+				T::DebitAuctionManagerHandler::new_debit_auction(params.issuance_currency_id, initial_amount, fix)
+			{
+				log::debug!(
+					target: "cdp-treasury",
+					"create_debit_auctions: Attempt to create debit auction for {:?} failed: {:?}",
+					fix, e
+				);
+				break;
+			}
+
+			unhandled_debit = unhandled_debit.saturating_sub(fix);
+			remaining_issuance = remaining_issuance.saturating_sub(initial_amount);
+			created_count += 1;
+		}
+		created_count
+	}
+
+	fn add_pending_insurance_contribution(amount: Balance) {
+		if !amount.is_zero() {
+			PendingInsuranceContribution::<T>::mutate(|pending| *pending = pending.saturating_add(amount));
+		}
+	}
+
+	fn contribute_to_insurance_fund() {
+		let pending = Self::pending_insurance_contribution();
+		if pending.is_zero() {
+			return;
+		}
+
+		let room = Self::insurance_fund_params().cap.saturating_sub(Self::insurance_fund());
+		let surplus = Self::surplus_pool();
+		let amount = pending.min(room).min(surplus);
+		if !amount.is_zero() {
+			if let Err(e) = T::Currency::transfer(
+				T::GetSetUSDId::get(),
+				&Self::account_id(),
+				&Self::insurance_fund_account_id(),
+				amount,
+			) {
+				log::warn!(
+					target: "cdp-treasury",
+					"contribute_to_insurance_fund: Attempt to deposit surplus {:?} to insurance fund failed: {:?}",
+					amount, e
+				);
+				return;
+			}
+			Self::deposit_event(Event::InsuranceFundDeposited { amount });
+		}
+
+		// the contribution exceeds the cap is dropped, the contribution short of
+		// surplus is kept pending
+		let remaining = if room < pending.min(surplus) {
+			Zero::zero()
+		} else {
+			pending.saturating_sub(amount)
+		};
+		PendingInsuranceContribution::<T>::put(remaining);
+	}
+
+	fn offset_surplus_and_debit() {
+		// Draw the insurance fund first to offset debit of `GetSetUSDId`.
+		let fund_offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::insurance_fund());
+		if !fund_offset_amount.is_zero() {
+			let res = T::Currency::withdraw(
+				T::GetSetUSDId::get(),
+				&Self::insurance_fund_account_id(),
+				fund_offset_amount,
+			);
+			match res {
+				Ok(_) => {
+					DebitPools::<T>::mutate(T::GetSetUSDId::get(), |debit| {
+						*debit = debit
+							.checked_sub(fund_offset_amount)
+							.expect("offset = min(debit, insurance fund); qed")
+					});
+					Self::deposit_event(Event::InsuranceFundDrawn {
+						amount: fund_offset_amount,
+					});
+				}
+				Err(e) => {
+					log::warn!(
+						target: "cdp-treasury",
+						"offset_surplus_and_debit: Attempt to burn insurance fund {:?} failed: {:?}, this is unexpected but should be safe",
+						fund_offset_amount, e
+					);
+				}
+			}
+		}
+
+		for stable_currency_id in Self::stable_currency_ids() {
+			Self::offset_surplus_and_debit_of(stable_currency_id);
+		}
+	}
+
+	fn offset_surplus_and_debit_of(stable_currency_id: CurrencyId) {
+		let offset_amount = sp_std::cmp::min(
+			Self::debit_pool_of(stable_currency_id),
+			Self::surplus_pool_of(stable_currency_id),
+		);
+
+		// Burn the amount that is equal to offset amount of stable currency.
+		if !offset_amount.is_zero() {
+			let res = T::Currency::withdraw(stable_currency_id, &Self::account_id(), offset_amount);
+			match res {
+				Ok(_) => {
+					DebitPools::<T>::mutate(stable_currency_id, |debit| {
+						*debit = debit
+							.checked_sub(offset_amount)
+							.expect("offset = min(debit, surplus); qed")
+					});
+				}
+				Err(e) => {
+					log::warn!(
+						target: "cdp-treasury",
+						"get_swap_supply_amount: Attempt to burn surplus {:?} failed: {:?}, this is unexpected but should be safe",
+						offset_amount, e
+					);
+				}
+			}
+		}
+	}
+}
+
+impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+
+	fn get_surplus_pool() -> Self::Balance {
 		Self::surplus_pool()
 	}
 
@@ -316,34 +2095,27 @@ impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
 		Self::total_collaterals(id)
 	}
 
+	// collateral is refunded against `GetSetUSDId` only after emergency
+	// shutdown, so the proportion is of its total issuance
 	fn get_debit_proportion(amount: Self::Balance) -> Ratio {
 		let stable_total_supply = T::Currency::total_issuance(T::GetSetUSDId::get());
 		Ratio::checked_from_rational(amount, stable_total_supply).unwrap_or_default()
 	}
 
 	fn on_system_debit(amount: Self::Balance) -> DispatchResult {
-		DebitPool::<T>::try_mutate(|debit_pool| -> DispatchResult {
-			*debit_pool = debit_pool.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
-			Ok(())
-		})
+		Self::on_system_debit_of(T::GetSetUSDId::get(), amount)
 	}
 
 	fn on_system_surplus(amount: Self::Balance) -> DispatchResult {
-		Self::issue_debit(&Self::account_id(), amount, true)
+		Self::on_system_surplus_of(T::GetSetUSDId::get(), amount)
 	}
 
 	fn issue_debit(who: &T::AccountId, debit: Self::Balance, backed: bool) -> DispatchResult {
-		// increase system debit if the debit is unbacked
-		if !backed {
-			Self::on_system_debit(debit)?;
-		}
-		T::Currency::deposit(T::GetSetUSDId::get(), who, debit)?;
-
-		Ok(())
+		Self::issue_debit_of(T::GetSetUSDId::get(), who, debit, backed)
 	}
 
 	fn burn_debit(who: &T::AccountId, debit: Self::Balance) -> DispatchResult {
-		T::Currency::withdraw(T::GetSetUSDId::get(), who, debit)
+		Self::burn_debit_of(T::GetSetUSDId::get(), who, debit)
 	}
 
 	fn deposit_surplus(from: &T::AccountId, surplus: Self::Balance) -> DispatchResult {
@@ -359,12 +2131,83 @@ impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
 	}
 }
 
-impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
-	fn swap_collateral_to_stable(
+impl<T: Config> MultiStableCurrencyTreasury<T::AccountId> for Pallet<T> {
+	fn get_stable_currency_ids() -> Vec<CurrencyId> {
+		Self::stable_currency_ids()
+	}
+
+	fn get_surplus_pool_of(stable_currency_id: CurrencyId) -> Balance {
+		Self::surplus_pool_of(stable_currency_id)
+	}
+
+	fn get_debit_pool_of(stable_currency_id: CurrencyId) -> Balance {
+		Self::debit_pool_of(stable_currency_id)
+	}
+
+	fn on_system_debit_of(stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		DebitPools::<T>::try_mutate(stable_currency_id, |debit_pool| -> DispatchResult {
+			*debit_pool = debit_pool.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})
+	}
+
+	#[transactional]
+	fn move_debit_of(
+		from_stable_currency_id: CurrencyId,
+		amount: Balance,
+		to_stable_currency_id: CurrencyId,
+		converted_amount: Balance,
+	) -> DispatchResult {
+		Self::ensure_stable_currency(from_stable_currency_id)?;
+		DebitPools::<T>::try_mutate(from_stable_currency_id, |debit_pool| -> DispatchResult {
+			*debit_pool = debit_pool
+				.checked_sub(amount)
+				.ok_or(Error::<T>::DebitPoolNotEnough)?;
+			Ok(())
+		})?;
+		Self::on_system_debit_of(to_stable_currency_id, converted_amount)
+	}
+
+	fn on_system_surplus_of(stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		Self::issue_debit_of(stable_currency_id, &Self::account_id(), amount, true)?;
+		// only the surplus of `GetSetUSDId` contributes to the insurance fund
+		if stable_currency_id == T::GetSetUSDId::get() {
+			let contribution = Self::insurance_fund_params().surplus_share.saturating_mul_int(amount);
+			Self::add_pending_insurance_contribution(contribution);
+		}
+		Ok(())
+	}
+
+	fn issue_debit_of(
+		stable_currency_id: CurrencyId,
+		who: &T::AccountId,
+		debit: Balance,
+		backed: bool,
+	) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		// increase system debit if the debit is unbacked
+		if !backed {
+			Self::on_system_debit_of(stable_currency_id, debit)?;
+		}
+		T::Currency::deposit(stable_currency_id, who, debit)?;
+
+		Ok(())
+	}
+
+	fn burn_debit_of(stable_currency_id: CurrencyId, who: &T::AccountId, debit: Balance) -> DispatchResult {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		T::Currency::withdraw(stable_currency_id, who, debit)
+	}
+
+	fn swap_collateral_to_stable_of(
+		stable_currency_id: CurrencyId,
 		currency_id: CurrencyId,
 		limit: SwapLimit<Balance>,
 		collateral_in_auction: bool,
 	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		Self::ensure_stable_currency(stable_currency_id)?;
+		Self::ensure_operation_not_paused(PausableOperation::SwapCollateralToStable, currency_id)?;
 		let supply_limit = match limit {
 			SwapLimit::ExactSupply(supply_amount, _) => supply_amount,
 			SwapLimit::ExactTarget(max_supply_amount, _) => max_supply_amount,
@@ -384,13 +2227,34 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 
 		let swap_path = T::DEX::get_best_price_swap_path(
 			currency_id,
-			T::GetSetUSDId::get(),
+			stable_currency_id,
 			limit,
-			T::AlternativeSwapPathJointList::get(),
+			Self::swap_path_joints(currency_id),
 		)
 		.ok_or(Error::<T>::CannotSwap)?;
+		Self::ensure_within_price_guard(currency_id, stable_currency_id, &swap_path, limit)?;
 		T::DEX::swap_with_specific_path(&Self::account_id(), &swap_path, limit)
 	}
+}
+
+impl<T: Config> OperationPauseManager for Pallet<T> {
+	fn ensure_operation_not_paused(operation: PausableOperation, currency_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			!Self::is_operation_paused(operation, currency_id),
+			Error::<T>::OperationPaused
+		);
+		Ok(())
+	}
+}
+
+impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
+	fn swap_collateral_to_stable(
+		currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+		collateral_in_auction: bool,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		Self::swap_collateral_to_stable_of(T::GetSetUSDId::get(), currency_id, limit, collateral_in_auction)
+	}
 
 	fn create_collateral_auctions(
 		currency_id: CurrencyId,
@@ -400,55 +2264,48 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 		splited: bool,
 	) -> DispatchResult {
 		ensure!(
-			Self::total_collaterals_not_in_auction(currency_id) >= amount,
+			Self::idle_collaterals(currency_id) >= amount,
 			Error::<T>::CollateralNotEnough,
 		);
+		if amount.is_zero() {
+			return Ok(());
+		}
 
-		let mut unhandled_collateral_amount = amount;
-		let mut unhandled_target = target;
-		let expected_collateral_auction_size = Self::expected_collateral_auction_size(currency_id);
-		let max_auctions_count: Balance = T::MaxAuctionsCount::get().into();
-		let lots_count = if !splited
-			|| max_auctions_count.is_zero()
-			|| expected_collateral_auction_size.is_zero()
-			|| amount <= expected_collateral_auction_size
-		{
-			One::one()
+		// all lots begin at once after emergency shutdown
+		let stagger_interval = if T::EmergencyShutdown::is_shutdown() {
+			Zero::zero()
 		} else {
-			let mut count = amount
-				.checked_div(expected_collateral_auction_size)
-				.expect("collateral auction maximum size is not zero; qed");
-
-			let remainder = amount
-				.checked_rem(expected_collateral_auction_size)
-				.expect("collateral auction maximum size is not zero; qed");
-			if !remainder.is_zero() {
-				count = count.saturating_add(One::one());
-			}
-			sp_std::cmp::min(count, max_auctions_count)
+			Self::lot_split_params(currency_id).stagger_interval
 		};
-		let average_amount_per_lot = amount.checked_div(lots_count).expect("lots count is at least 1; qed");
-		let average_target_per_lot = target.checked_div(lots_count).expect("lots count is at least 1; qed");
-		let mut created_lots: Balance = Zero::zero();
-
-		while !unhandled_collateral_amount.is_zero() {
-			created_lots = created_lots.saturating_add(One::one());
-			let (lot_collateral_amount, lot_target) = if created_lots == lots_count {
-				// the last lot may be have some remnant than average
-				(unhandled_collateral_amount, unhandled_target)
-			} else {
-				(average_amount_per_lot, average_target_per_lot)
-			};
-
-			T::AuctionManagerHandler::new_collateral_auction(
-				&refund_receiver,
-				currency_id,
-				lot_collateral_amount,
-				lot_target,
-			)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		let lots = Self::split_collateral_auction_lots(currency_id, amount, target, splited);
 
-			unhandled_collateral_amount = unhandled_collateral_amount.saturating_sub(lot_collateral_amount);
-			unhandled_target = unhandled_target.saturating_sub(lot_target);
+		for (index, (lot_amount, lot_target)) in lots.into_iter().enumerate() {
+			if index == 0 || stagger_interval.is_zero() {
+				T::AuctionManagerHandler::new_collateral_auction(&refund_receiver, currency_id, lot_amount, lot_target)?;
+			} else {
+				// the following lots begin one by one every stagger interval
+				let start_at = now.saturating_add(stagger_interval.saturating_mul((index as u32).into()));
+				PendingCollateralLots::<T>::append(
+					start_at,
+					PendingCollateralLot {
+						refund_receiver: refund_receiver.clone(),
+						currency_id,
+						amount: lot_amount,
+						target: lot_target,
+					},
+				);
+				TotalCollateralInPendingLots::<T>::mutate(currency_id, |total| {
+					*total = total.saturating_add(lot_amount)
+				});
+				TotalTargetInPendingLots::<T>::mutate(|total| *total = total.saturating_add(lot_target));
+				Self::deposit_event(Event::CollateralAuctionLotScheduled {
+					collateral_type: currency_id,
+					amount: lot_amount,
+					target: lot_target,
+					start_at,
+				});
+			}
 		}
 		Ok(())
 	}
@@ -476,6 +2333,100 @@ impl<T: Config> CDPTreasuryExtended<T::AccountId> for Pallet<T> {
 
 }
 
+impl<T: Config> InsuranceFundManager for Pallet<T> {
+	fn on_liquidation_penalty(stable_currency_id: CurrencyId, penalty: Balance) {
+		if let Some(value) = Self::default_stable_value_of(stable_currency_id, penalty) {
+			let contribution = Self::insurance_fund_params().penalty_share.saturating_mul_int(value);
+			Self::add_pending_insurance_contribution(contribution);
+		}
+	}
+
+	fn on_liquidation_penalty_auctioned(currency_id: CurrencyId, penalty: Balance, target: Balance) {
+		if !target.is_zero() {
+			LiquidationPenaltiesInAuction::<T>::mutate(currency_id, |(total_penalty, total_target)| {
+				*total_penalty = total_penalty.saturating_add(penalty);
+				*total_target = total_target.saturating_add(target);
+			});
+		}
+	}
+}
+
+impl<T: Config> AuctionProceedsManager<T::AccountId> for Pallet<T> {
+	fn return_excess_proceeds(currency_id: CurrencyId, to: &T::AccountId, amount: Balance) -> DispatchResult {
+		// the proceeds of the collateral owned by cdp treasury are its surplus already
+		if *to == Self::account_id() {
+			return Ok(());
+		}
+		T::Currency::transfer(T::GetSetUSDId::get(), &Self::account_id(), to, amount)
+	}
+
+	fn on_auction_proceeds(
+		currency_id: CurrencyId,
+		refund_recipient: &T::AccountId,
+		debt_owed: Balance,
+		proceeds: Balance,
+	) {
+		if *refund_recipient == Self::account_id() || debt_owed.is_zero() {
+			return;
+		}
+		let covered = proceeds.min(debt_owed);
+		Self::on_collateral_recovered(currency_id, T::GetSetUSDId::get(), covered);
+
+		// the auction takes its share of the penalty by its debt owed, and realises
+		// the part covered by the proceeds
+		let (total_penalty, total_target) = Self::liquidation_penalties_in_auction(currency_id);
+		let auction_penalty = Ratio::checked_from_rational(debt_owed, total_target)
+			.map_or(total_penalty, |ratio| ratio.saturating_mul_int(total_penalty))
+			.min(total_penalty);
+		let realised_penalty = Ratio::checked_from_rational(covered, debt_owed)
+			.unwrap_or_default()
+			.saturating_mul_int(auction_penalty);
+		let remaining_target = total_target.saturating_sub(debt_owed);
+		if remaining_target.is_zero() {
+			LiquidationPenaltiesInAuction::<T>::remove(currency_id);
+		} else {
+			LiquidationPenaltiesInAuction::<T>::insert(
+				currency_id,
+				(total_penalty.saturating_sub(auction_penalty), remaining_target),
+			);
+		}
+		Self::on_liquidation_penalty(T::GetSetUSDId::get(), realised_penalty);
+	}
+
+	fn get_collateral_swap_amount(currency_id: CurrencyId, limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+		let swap_path = T::DEX::get_best_price_swap_path(
+			currency_id,
+			T::GetSetUSDId::get(),
+			limit,
+			Self::swap_path_joints(currency_id),
+		)?;
+		T::DEX::get_swap_amount(&swap_path, limit)
+	}
+}
+
+impl<T: Config> BadDebtManager for Pallet<T> {
+	fn on_collateral_debit(currency_id: CurrencyId, stable_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		Self::on_system_debit_of(stable_currency_id, amount)?;
+		if let Some(value) = Self::default_stable_value_of(stable_currency_id, amount) {
+			BadDebtReports::<T>::mutate(currency_id, |report| {
+				report.bad_debt = report.bad_debt.saturating_add(value);
+			});
+		}
+		Ok(())
+	}
+
+	fn on_collateral_recovered(currency_id: CurrencyId, stable_currency_id: CurrencyId, amount: Balance) {
+		if amount.is_zero() {
+			return;
+		}
+		if let Some(value) = Self::default_stable_value_of(stable_currency_id, amount) {
+			BadDebtReports::<T>::mutate(currency_id, |report| {
+				report.recovered = report.recovered.saturating_add(value);
+			});
+		}
+	}
+}
+
 #[cfg(feature = "std")]
 impl GenesisConfig {
 	/// Direct implementation of `GenesisBuild::build_storage`.
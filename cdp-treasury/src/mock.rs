@@ -18,7 +18,7 @@ use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 use sp_std::cell::RefCell;
 
-use support::SerpTreasury;
+use support::{EmergencyShutdown, Price, PriceProvider, SerpTreasury};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -150,6 +150,7 @@ impl module_dex::Config for Runtime {
 thread_local! {
 	pub static TOTAL_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_COLLATERAL_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	pub static TOTAL_TARGET_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
 }
 
 pub struct MockAuctionManager;
@@ -162,10 +163,11 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		_refund_recipient: &AccountId,
 		_currency_id: Self::CurrencyId,
 		amount: Self::Balance,
-		_target: Self::Balance,
+		target: Self::Balance,
 	) -> DispatchResult {
 		TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut() += 1);
 		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() += amount);
+		TOTAL_TARGET_IN_AUCTION.with(|v| *v.borrow_mut() += target);
 		Ok(())
 	}
 
@@ -178,7 +180,51 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 	}
 
 	fn get_total_target_in_auction() -> Self::Balance {
-		unimplemented!()
+		TOTAL_TARGET_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
+}
+
+thread_local! {
+	pub static TOTAL_DEBIT_AUCTION: RefCell<u32> = RefCell::new(0);
+	pub static TOTAL_DEBIT_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	pub static TOTAL_ISSUANCE_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+}
+
+pub struct MockDebitAuctionManager;
+impl DebitAuctionManager for MockDebitAuctionManager {
+	fn new_debit_auction(_currency_id: CurrencyId, initial_amount: Balance, fix: Balance) -> DispatchResult {
+		TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut() += 1);
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut() += fix);
+		TOTAL_ISSUANCE_IN_AUCTION.with(|v| *v.borrow_mut() += initial_amount);
+		Ok(())
+	}
+
+	fn get_total_debit_in_auction() -> Balance {
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
+
+	fn get_total_issuance_in_auction(_currency_id: CurrencyId) -> Balance {
+		TOTAL_ISSUANCE_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
+}
+
+thread_local! {
+	static RELATIVE_PRICE: RefCell<Option<Price>> = RefCell::new(None);
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_relative_price(price: Option<Price>) {
+		RELATIVE_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		RELATIVE_PRICE.with(|v| *v.borrow_mut())
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
 	}
 }
 
@@ -254,10 +300,10 @@ impl SerpTreasury<AccountId> for MockSerpTreasury {
 
 	/// issue serpup surplus(stable currencies) to their destinations according to the serpup_ratio.
 	fn on_serplus(
-		_currency_id: CurrencyId,
-		_amount: Balance,
+		currency_id: CurrencyId,
+		amount: Balance,
 	) -> DispatchResult {
-		unimplemented!()
+		Currencies::withdraw(currency_id, &CDPTreasuryModule::account_id(), amount)
 	}
 
 	/// issue serpup surplus(stable currencies) to their destinations according to the serpup_ratio.
@@ -338,11 +384,13 @@ impl SerpTreasury<AccountId> for MockSerpTreasury {
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 	pub const MaxAuctionsCount: u32 = 5;
+	pub const BalanceSheetSnapshotCapacity: u32 = 3;
 }
 
 parameter_types! {
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");
 	pub const TreasuryAccount: AccountId = 10;
+	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![SERP, DNAR];
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
 		vec![DNAR],
 	];
@@ -352,17 +400,34 @@ thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
 }
 
+pub fn mock_shutdown() {
+	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
+}
+
+pub struct MockEmergencyShutdown;
+impl EmergencyShutdown for MockEmergencyShutdown {
+	fn is_shutdown() -> bool {
+		IS_SHUTDOWN.with(|v| *v.borrow_mut())
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type GetSetUSDId = GetSetUSDId;
+	type StableCurrencyIds = StableCurrencyIds;
 	type AuctionManagerHandler = MockAuctionManager;
+	type DebitAuctionManagerHandler = MockDebitAuctionManager;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type DEX = DEXModule;
+	type PriceSource = MockPriceSource;
+	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type SerpTreasury = MockSerpTreasury;
 	type UpdateOrigin = EnsureOneOf<AccountId, EnsureRoot<AccountId>, EnsureSignedBy<One, AccountId>>;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type BalanceSheetSnapshotCapacity = BalanceSheetSnapshotCapacity;
 	type WeightInfo = ();
 }
 
@@ -115,6 +115,207 @@ fn burn_debit_work() {
 	});
 }
 
+#[test]
+fn multi_stable_currency_debit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			<CDPTreasuryModule as MultiStableCurrencyTreasury<AccountId>>::get_stable_currency_ids(),
+			vec![SETUSD, SETR]
+		);
+		assert_noop!(
+			CDPTreasuryModule::issue_debit_of(DNAR, &ALICE, 1000, false),
+			Error::<Runtime>::InvalidStableCurrencyType
+		);
+
+		let setr_before = Currencies::free_balance(SETR, &ALICE);
+		assert_ok!(CDPTreasuryModule::issue_debit_of(SETR, &ALICE, 1000, false));
+		assert_eq!(Currencies::free_balance(SETR, &ALICE), setr_before + 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool_of(SETR), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+
+		assert_ok!(CDPTreasuryModule::burn_debit_of(SETR, &ALICE, 300));
+		assert_eq!(Currencies::free_balance(SETR, &ALICE), setr_before + 700);
+
+		// surplus of a stable currency only offsets the debit of itself
+		assert_ok!(CDPTreasuryModule::on_system_surplus(500));
+		assert_ok!(CDPTreasuryModule::on_system_surplus_of(SETR, 400));
+		assert_eq!(CDPTreasuryModule::surplus_pool_of(SETR), 400);
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(CDPTreasuryModule::surplus_pool_of(SETR), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool_of(SETR), 600);
+	});
+}
+
+#[test]
+fn pause_operation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, SERP, 200));
+		assert_noop!(
+			CDPTreasuryModule::pause_operation(
+				Origin::signed(5),
+				PausableOperation::SwapCollateralToStable,
+				Some(SERP),
+				None
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::pause_operation(
+				Origin::signed(1),
+				PausableOperation::SwapCollateralToStable,
+				Some(SERP),
+				Some(1)
+			),
+			Error::<Runtime>::InvalidUnpauseBlock
+		);
+
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(SERP),
+			Some(10)
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::OperationPaused {
+			operation: PausableOperation::SwapCollateralToStable,
+			collateral_type: Some(SERP),
+			unpause_at: Some(10),
+		}));
+		assert!(CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			SERP
+		));
+		assert!(!CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			DNAR
+		));
+		assert_noop!(
+			CDPTreasuryModule::swap_collateral_to_stable(SERP, SwapLimit::ExactSupply(100, 0), false),
+			Error::<Runtime>::OperationPaused
+		);
+
+		// automatically unpaused at the scheduled block
+		CDPTreasuryModule::on_initialize(9);
+		assert!(CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			SERP
+		));
+		CDPTreasuryModule::on_initialize(10);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::OperationUnpaused {
+			operation: PausableOperation::SwapCollateralToStable,
+			collateral_type: Some(SERP),
+		}));
+		assert_eq!(
+			CDPTreasuryModule::paused_operation(PausableOperation::SwapCollateralToStable, Some(SERP)),
+			None
+		);
+
+		// pause for all collateral types until unpaused by governance
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			None,
+			None
+		));
+		assert!(CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			DNAR
+		));
+		assert_ok!(CDPTreasuryModule::unpause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			None
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::OperationUnpaused {
+			operation: PausableOperation::SwapCollateralToStable,
+			collateral_type: None,
+		}));
+		assert!(!CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			DNAR
+		));
+	});
+}
+
+#[test]
+fn pause_operation_replace_scheduled_unpause() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(SERP),
+			Some(10)
+		));
+		assert_eq!(
+			CDPTreasuryModule::scheduled_unpauses(10),
+			vec![(PausableOperation::SwapCollateralToStable, Some(SERP))]
+		);
+
+		// pause again with another schedule replaces the previous one
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(SERP),
+			Some(20)
+		));
+		assert!(!ScheduledUnpauses::<Runtime>::contains_key(10));
+		assert_eq!(
+			CDPTreasuryModule::scheduled_unpauses(20),
+			vec![(PausableOperation::SwapCollateralToStable, Some(SERP))]
+		);
+		CDPTreasuryModule::on_initialize(10);
+		assert!(CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			SERP
+		));
+
+		// pause again without schedule removes the previous one
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(SERP),
+			None
+		));
+		assert!(!ScheduledUnpauses::<Runtime>::contains_key(20));
+		CDPTreasuryModule::on_initialize(20);
+		assert!(CDPTreasuryModule::is_operation_paused(
+			PausableOperation::SwapCollateralToStable,
+			SERP
+		));
+
+		// manual unpause removes the schedule
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(SERP),
+			Some(30)
+		));
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(DNAR),
+			Some(30)
+		));
+		assert_ok!(CDPTreasuryModule::unpause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(SERP)
+		));
+		assert_eq!(
+			CDPTreasuryModule::scheduled_unpauses(30),
+			vec![(PausableOperation::SwapCollateralToStable, Some(DNAR))]
+		);
+		assert_ok!(CDPTreasuryModule::unpause_operation(
+			Origin::signed(1),
+			PausableOperation::SwapCollateralToStable,
+			Some(DNAR)
+		));
+		assert!(!ScheduledUnpauses::<Runtime>::contains_key(30));
+	});
+}
+
 #[test]
 fn deposit_surplus_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -435,3 +636,1133 @@ fn exchange_collateral_to_stable_work() {
 		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(SERP), 67);
 	});
 }
+
+#[test]
+fn set_surplus_buffer_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::surplus_buffer(), 0);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_buffer(Origin::signed(5), 300),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer(Origin::signed(1), 300));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::SurplusBufferUpdated {
+			new_buffer: 300,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_buffer(), 300);
+	});
+}
+
+#[test]
+fn set_surplus_extraction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::surplus_extraction_period(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_extraction_cap_per_period(), None);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_extraction_params(Origin::signed(5), 10, Some(400)),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_surplus_extraction_params(
+			Origin::signed(1),
+			10,
+			Some(400)
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(
+			crate::Event::SurplusExtractionParamsUpdated {
+				new_period: 10,
+				new_cap_per_period: Some(400),
+			},
+		));
+		assert_eq!(CDPTreasuryModule::surplus_extraction_period(), 10);
+		assert_eq!(CDPTreasuryModule::surplus_extraction_cap_per_period(), Some(400));
+	});
+}
+
+#[test]
+fn extract_excess_surplus_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::on_system_debit(100));
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer(Origin::signed(1), 300));
+		assert_eq!(CDPTreasuryModule::excess_surplus(), 600);
+		assert_ok!(CDPTreasuryModule::on_system_surplus_of(SETR, 50));
+		assert_eq!(CDPTreasuryModule::excess_surplus_of(SETR), 50);
+
+		// disabled when the period is 0
+		CDPTreasuryModule::on_initialize(10);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_extraction_params(
+			Origin::signed(1),
+			10,
+			Some(400)
+		));
+		CDPTreasuryModule::on_initialize(15);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
+
+		// capped by the cap per period
+		CDPTreasuryModule::on_initialize(20);
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusExtractedToSerp {
+			currency_id: SETUSD,
+			amount: 400,
+		}));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::SurplusExtractedToSerp {
+			currency_id: SETR,
+			amount: 50,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 600);
+		assert_eq!(CDPTreasuryModule::surplus_pool_of(SETR), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+
+		// only the surplus above the debit pool and buffer is extracted
+		CDPTreasuryModule::on_initialize(30);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::SurplusExtractedToSerp {
+			currency_id: SETUSD,
+			amount: 200,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 400);
+		assert_eq!(CDPTreasuryModule::excess_surplus(), 0);
+
+		CDPTreasuryModule::on_initialize(40);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 400);
+	});
+}
+
+#[test]
+fn set_debit_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = DebitAuctionParams {
+			issuance_currency_id: DNAR,
+			trigger_threshold: 100,
+			lot_size: 200,
+			initial_issuance_per_lot: 50,
+			max_issuance: 120,
+		};
+		assert_eq!(CDPTreasuryModule::debit_auction_params(), None);
+		assert_noop!(
+			CDPTreasuryModule::set_debit_auction_params(Origin::signed(5), Some(params.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_debit_auction_params(
+				Origin::signed(1),
+				Some(DebitAuctionParams {
+					lot_size: 0,
+					..params.clone()
+				})
+			),
+			Error::<Runtime>::InvalidDebitAuctionParams
+		);
+		assert_ok!(CDPTreasuryModule::set_debit_auction_params(
+			Origin::signed(1),
+			Some(params.clone())
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::DebitAuctionParamsUpdated {
+			new_params: Some(params.clone()),
+		}));
+		assert_eq!(CDPTreasuryModule::debit_auction_params(), Some(params));
+
+		assert_ok!(CDPTreasuryModule::set_debit_auction_params(Origin::signed(1), None));
+		assert_eq!(CDPTreasuryModule::debit_auction_params(), None);
+	});
+}
+
+#[test]
+fn create_debit_auctions_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::on_system_debit(1000));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(300));
+		assert_eq!(CDPTreasuryModule::uncovered_debit(), 700);
+
+		// disabled without debit auction params
+		CDPTreasuryModule::on_initialize(1);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		let params = DebitAuctionParams {
+			issuance_currency_id: DNAR,
+			trigger_threshold: 100,
+			lot_size: 200,
+			initial_issuance_per_lot: 50,
+			max_issuance: 120,
+		};
+		assert_ok!(CDPTreasuryModule::set_debit_auction_params(
+			Origin::signed(1),
+			Some(params.clone())
+		));
+
+		// limited by max issuance
+		CDPTreasuryModule::on_initialize(2);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 2);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 400);
+		assert_eq!(TOTAL_ISSUANCE_IN_AUCTION.with(|v| *v.borrow_mut()), 100);
+		assert_eq!(CDPTreasuryModule::uncovered_debit(), 300);
+
+		assert_ok!(CDPTreasuryModule::set_debit_auction_params(
+			Origin::signed(1),
+			Some(DebitAuctionParams {
+				max_issuance: 1000,
+				..params
+			})
+		));
+
+		// the last lot issues proportionally
+		CDPTreasuryModule::on_initialize(3);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 4);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 700);
+		assert_eq!(TOTAL_ISSUANCE_IN_AUCTION.with(|v| *v.borrow_mut()), 175);
+		assert_eq!(CDPTreasuryModule::uncovered_debit(), 0);
+
+		// not triggered when the uncovered debit is below the threshold
+		assert_ok!(CDPTreasuryModule::on_system_debit(100));
+		CDPTreasuryModule::on_initialize(4);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 4);
+	});
+}
+
+#[test]
+fn set_collateral_sell_policy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let policy = CollateralSellPolicy {
+			threshold: 100,
+			max_sell_amount_per_period: 50,
+			max_swap_slippage: Ratio::saturating_from_rational(10, 100),
+		};
+		assert_eq!(CDPTreasuryModule::collateral_sell_policy(SERP), None);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_sell_policy(Origin::signed(5), SERP, Some(policy.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_sell_policy(
+				Origin::signed(1),
+				SERP,
+				Some(CollateralSellPolicy {
+					max_swap_slippage: Ratio::saturating_from_rational(101, 100),
+					..policy.clone()
+				})
+			),
+			Error::<Runtime>::InvalidCollateralSellPolicy
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_sell_policy(
+			Origin::signed(1),
+			SERP,
+			Some(policy.clone())
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::CollateralSellPolicyUpdated {
+			collateral_type: SERP,
+			new_policy: Some(policy.clone()),
+		}));
+		assert_eq!(CDPTreasuryModule::collateral_sell_policy(SERP), Some(policy));
+
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_sell_period(Origin::signed(5), 10),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_sell_period(Origin::signed(1), 10));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::CollateralSellPeriodUpdated {
+			new_period: 10,
+		}));
+		assert_eq!(CDPTreasuryModule::collateral_sell_period(), 10);
+	});
+}
+
+#[test]
+fn sell_idle_collaterals_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(BOB),
+			SERP,
+			SETUSD,
+			200,
+			1000,
+			0,
+		));
+		assert_ok!(Currencies::deposit(SERP, &CDPTreasuryModule::account_id(), 1000));
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(5)));
+		let policy = CollateralSellPolicy {
+			threshold: 100,
+			max_sell_amount_per_period: 50,
+			max_swap_slippage: Ratio::saturating_from_rational(10, 100),
+		};
+		assert_ok!(CDPTreasuryModule::set_collateral_sell_policy(
+			Origin::signed(1),
+			SERP,
+			Some(policy.clone())
+		));
+		assert_ok!(CDPTreasuryModule::set_collateral_sell_period(Origin::signed(1), 10));
+
+		CDPTreasuryModule::on_initialize(5);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		// DEX price is out of the oracle slippage, sell by collateral auction
+		CDPTreasuryModule::on_initialize(10);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::IdleCollateralAuctioned {
+			collateral_type: SERP,
+			collateral_amount: 50,
+		}));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(SERP), 950);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		// DEX price is within the oracle slippage, sell by DEX
+		assert_ok!(CDPTreasuryModule::set_collateral_sell_policy(
+			Origin::signed(1),
+			SERP,
+			Some(CollateralSellPolicy {
+				max_swap_slippage: Ratio::saturating_from_rational(25, 100),
+				..policy.clone()
+			})
+		));
+		CDPTreasuryModule::on_initialize(20);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::IdleCollateralSwapped {
+			collateral_type: SERP,
+			supply_collateral_amount: 50,
+			target_stable_amount: 200,
+		}));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(SERP), 900);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+
+		// idle collateral is below the threshold
+		assert_ok!(CDPTreasuryModule::set_collateral_sell_policy(
+			Origin::signed(1),
+			SERP,
+			Some(CollateralSellPolicy {
+				threshold: 1000,
+				..policy
+			})
+		));
+		CDPTreasuryModule::on_initialize(30);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(SERP), 900);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+	});
+}
+
+#[test]
+fn bad_debt_report_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP), BadDebtReport::default());
+		assert_ok!(CDPTreasuryModule::on_collateral_debit(SERP, SETUSD, 300));
+		assert_ok!(CDPTreasuryModule::on_system_debit(100));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 400);
+		assert_eq!(
+			CDPTreasuryModule::bad_debt_report(SERP),
+			BadDebtReport {
+				bad_debt: 300,
+				recovered: 0,
+			}
+		);
+
+		CDPTreasuryModule::on_collateral_recovered(SERP, SETUSD, 30);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 30);
+
+		// swapping the idle collateral to stable recovers nothing
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(BOB),
+			SERP,
+			SETUSD,
+			200,
+			1000,
+			0,
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, SERP, 100));
+		assert_eq!(
+			CDPTreasuryModule::swap_collateral_to_stable(SERP, SwapLimit::ExactSupply(50, 0), false).unwrap(),
+			(50, 200)
+		);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 30);
+
+		// the auction proceeds of the collateral owned by cdp treasury recover nothing
+		CDPTreasuryModule::on_auction_proceeds(SERP, &CDPTreasuryModule::account_id(), 100, 100);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 30);
+		CDPTreasuryModule::on_auction_proceeds(SERP, &ALICE, 170, 200);
+		assert_eq!(
+			CDPTreasuryModule::bad_debt_report(SERP),
+			BadDebtReport {
+				bad_debt: 300,
+				recovered: 200,
+			}
+		);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).net_loss(), 100);
+
+		// the other stable currencies are counted in the default stable currency
+		CDPTreasuryModule::on_collateral_recovered(SERP, SETR, 30);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 200);
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 2)));
+		CDPTreasuryModule::on_collateral_recovered(SERP, SETR, 30);
+		assert_ok!(CDPTreasuryModule::on_collateral_debit(SERP, SETR, 100));
+		assert_eq!(
+			CDPTreasuryModule::bad_debt_reports(),
+			vec![(
+				SERP,
+				BadDebtReport {
+					bad_debt: 350,
+					recovered: 215,
+				}
+			)]
+		);
+	});
+}
+
+#[test]
+fn set_insurance_fund_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = InsuranceFundParams {
+			penalty_share: Ratio::saturating_from_rational(50, 100),
+			surplus_share: Ratio::saturating_from_rational(10, 100),
+			cap: 100,
+		};
+		assert_eq!(CDPTreasuryModule::insurance_fund_params(), InsuranceFundParams::default());
+		assert_noop!(
+			CDPTreasuryModule::set_insurance_fund_params(Origin::signed(5), params.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_insurance_fund_params(
+				Origin::signed(1),
+				InsuranceFundParams {
+					surplus_share: Ratio::saturating_from_rational(101, 100),
+					..params.clone()
+				}
+			),
+			Error::<Runtime>::InvalidInsuranceFundParams
+		);
+		assert_ok!(CDPTreasuryModule::set_insurance_fund_params(
+			Origin::signed(1),
+			params.clone()
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::InsuranceFundParamsUpdated {
+			new_params: params.clone(),
+		}));
+		assert_eq!(CDPTreasuryModule::insurance_fund_params(), params);
+	});
+}
+
+#[test]
+fn insurance_fund_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::set_insurance_fund_params(
+			Origin::signed(1),
+			InsuranceFundParams {
+				penalty_share: Ratio::saturating_from_rational(50, 100),
+				surplus_share: Ratio::saturating_from_rational(10, 100),
+				cap: 100,
+			}
+		));
+
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		CDPTreasuryModule::on_liquidation_penalty(SETUSD, 40);
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 120);
+		assert_eq!(CDPTreasuryModule::excess_surplus(), 880);
+
+		// the contribution exceeds the cap is dropped
+		CDPTreasuryModule::on_finalize(1);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::InsuranceFundDeposited {
+			amount: 100,
+		}));
+		assert_eq!(CDPTreasuryModule::insurance_fund(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 900);
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 0);
+
+		assert_noop!(
+			CDPTreasuryModule::release_insurance_fund(Origin::signed(5), 30),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::release_insurance_fund(Origin::signed(1), 101),
+			Error::<Runtime>::InsuranceFundNotEnough
+		);
+		assert_ok!(CDPTreasuryModule::release_insurance_fund(Origin::signed(1), 30));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::InsuranceFundReleased {
+			amount: 30,
+		}));
+		assert_eq!(CDPTreasuryModule::insurance_fund(), 70);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 930);
+
+		// the insurance fund is drawn first to offset debit
+		assert_ok!(CDPTreasuryModule::on_system_debit(150));
+		CDPTreasuryModule::on_finalize(2);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::InsuranceFundDrawn {
+			amount: 70,
+		}));
+		assert_eq!(CDPTreasuryModule::insurance_fund(), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 850);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+
+		assert_ok!(CDPTreasuryModule::on_system_surplus(100));
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 10);
+		CDPTreasuryModule::on_finalize(3);
+		assert_eq!(CDPTreasuryModule::insurance_fund(), 10);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 940);
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 0);
+	});
+}
+
+#[test]
+fn insurance_fund_contribution_limited_by_surplus_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::set_insurance_fund_params(
+			Origin::signed(1),
+			InsuranceFundParams {
+				penalty_share: Ratio::saturating_from_rational(50, 100),
+				surplus_share: Ratio::saturating_from_rational(10, 100),
+				cap: 1000,
+			}
+		));
+
+		assert_ok!(CDPTreasuryModule::on_system_surplus(50));
+		CDPTreasuryModule::on_liquidation_penalty(SETUSD, 400);
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 205);
+
+		// the contribution short of surplus is kept pending
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(CDPTreasuryModule::insurance_fund(), 50);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 155);
+
+		assert_ok!(CDPTreasuryModule::on_system_surplus(100));
+		CDPTreasuryModule::on_finalize(2);
+		assert_eq!(CDPTreasuryModule::insurance_fund(), 150);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 65);
+	});
+}
+
+#[test]
+fn liquidation_penalty_realised_by_auction_proceeds() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::set_insurance_fund_params(
+			Origin::signed(1),
+			InsuranceFundParams {
+				penalty_share: Ratio::saturating_from_rational(50, 100),
+				surplus_share: Ratio::zero(),
+				cap: 1000,
+			}
+		));
+
+		CDPTreasuryModule::on_liquidation_penalty_auctioned(SERP, 20, 120);
+		assert_eq!(CDPTreasuryModule::liquidation_penalties_in_auction(SERP), (20, 120));
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 0);
+
+		// the proceeds of the collateral owned by cdp treasury realise nothing
+		CDPTreasuryModule::on_auction_proceeds(SERP, &CDPTreasuryModule::account_id(), 60, 60);
+		assert_eq!(CDPTreasuryModule::liquidation_penalties_in_auction(SERP), (20, 120));
+
+		// the lot covering its debt owed realises its share of the penalty
+		CDPTreasuryModule::on_auction_proceeds(SERP, &ALICE, 60, 80);
+		assert_eq!(CDPTreasuryModule::liquidation_penalties_in_auction(SERP), (10, 60));
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 5);
+
+		// the lot short of its debt owed realises the penalty in proportion
+		CDPTreasuryModule::on_auction_proceeds(SERP, &ALICE, 60, 36);
+		assert_eq!(CDPTreasuryModule::liquidation_penalties_in_auction(SERP), (0, 0));
+		assert_eq!(CDPTreasuryModule::pending_insurance_contribution(), 8);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).recovered, 96);
+	});
+}
+
+#[test]
+fn set_swap_path_joint_list_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::swap_path_joint_list(SERP), None);
+		assert_eq!(CDPTreasuryModule::swap_path_joints(SERP), vec![vec![DNAR]]);
+		assert_noop!(
+			CDPTreasuryModule::set_swap_path_joint_list(Origin::signed(5), SERP, Some(vec![])),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_swap_path_joint_list(
+			Origin::signed(1),
+			SERP,
+			Some(vec![])
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::SwapPathJointListUpdated {
+			collateral_type: SERP,
+			new_list: Some(vec![]),
+		}));
+		assert_eq!(CDPTreasuryModule::swap_path_joints(SERP), Vec::<Vec<CurrencyId>>::new());
+		assert_eq!(CDPTreasuryModule::swap_path_joints(DNAR), vec![vec![DNAR]]);
+
+		assert_ok!(CDPTreasuryModule::set_swap_path_joint_list(Origin::signed(1), SERP, None));
+		assert_eq!(CDPTreasuryModule::swap_path_joints(SERP), vec![vec![DNAR]]);
+	});
+}
+
+#[test]
+fn set_swap_price_guard_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let guard = SwapPriceGuard {
+			max_deviation: Ratio::saturating_from_rational(10, 100),
+			twap_chunk_size: 10,
+			twap_interval: 2,
+		};
+		assert_eq!(CDPTreasuryModule::swap_price_guard(SERP), None);
+		assert_noop!(
+			CDPTreasuryModule::set_swap_price_guard(Origin::signed(5), SERP, Some(guard.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_swap_price_guard(
+				Origin::signed(1),
+				SERP,
+				Some(SwapPriceGuard {
+					max_deviation: Ratio::saturating_from_rational(101, 100),
+					..guard.clone()
+				})
+			),
+			Error::<Runtime>::InvalidSwapPriceGuard
+		);
+		assert_ok!(CDPTreasuryModule::set_swap_price_guard(
+			Origin::signed(1),
+			SERP,
+			Some(guard.clone())
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::SwapPriceGuardUpdated {
+			collateral_type: SERP,
+			new_guard: Some(guard.clone()),
+		}));
+		assert_eq!(CDPTreasuryModule::swap_price_guard(SERP), Some(guard));
+	});
+}
+
+#[test]
+fn swap_price_guard_and_twap_sell_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(BOB),
+			SERP,
+			SETUSD,
+			200,
+			1000,
+			0,
+		));
+		assert_ok!(Currencies::deposit(SERP, &CDPTreasuryModule::account_id(), 1000));
+		assert_ok!(CDPTreasuryModule::set_swap_price_guard(
+			Origin::signed(1),
+			SERP,
+			Some(SwapPriceGuard {
+				max_deviation: Ratio::saturating_from_rational(10, 100),
+				twap_chunk_size: 0,
+				twap_interval: 2,
+			})
+		));
+
+		// the guard fails without oracle price
+		assert_noop!(
+			CDPTreasuryModule::exchange_collateral_to_stable(Origin::signed(1), SERP, SwapLimit::ExactSupply(50, 0)),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+
+		// the DEX price of swapping 50 is 4, which is 20% below the oracle price
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(5)));
+		assert_noop!(
+			CDPTreasuryModule::exchange_collateral_to_stable(Origin::signed(1), SERP, SwapLimit::ExactSupply(50, 0)),
+			Error::<Runtime>::ExceedPriceDeviation
+		);
+
+		// split the sell exceeding the guard into swaps of 10
+		assert_ok!(CDPTreasuryModule::set_swap_price_guard(
+			Origin::signed(1),
+			SERP,
+			Some(SwapPriceGuard {
+				max_deviation: Ratio::saturating_from_rational(10, 100),
+				twap_chunk_size: 10,
+				twap_interval: 2,
+			})
+		));
+		assert_ok!(CDPTreasuryModule::exchange_collateral_to_stable(
+			Origin::signed(1),
+			SERP,
+			SwapLimit::ExactSupply(30, 0)
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::TwapSellScheduled {
+			collateral_type: SERP,
+			collateral_amount: 30,
+		}));
+		assert_eq!(
+			CDPTreasuryModule::twap_sell_order(SERP),
+			Some(TwapSellOrder {
+				remaining: 30,
+				next_swap_at: 1,
+			})
+		);
+		assert_eq!(CDPTreasuryModule::idle_collaterals(SERP), 970);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+
+		// the collateral reserved by the split sell cannot be auctioned
+		assert_noop!(
+			CDPTreasuryModule::create_collateral_auctions(SERP, 971, 1000, ALICE, true),
+			Error::<Runtime>::CollateralNotEnough,
+		);
+
+		CDPTreasuryModule::on_initialize(1);
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::TwapSellExecuted {
+			collateral_type: SERP,
+			supply_collateral_amount: 10,
+			target_stable_amount: 47,
+			remaining_collateral_amount: 20,
+		}));
+		assert_eq!(
+			CDPTreasuryModule::twap_sell_order(SERP),
+			Some(TwapSellOrder {
+				remaining: 20,
+				next_swap_at: 3,
+			})
+		);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 47);
+		assert_eq!(CDPTreasuryModule::total_collaterals(SERP), 990);
+
+		// the next swap is not due
+		CDPTreasuryModule::on_initialize(2);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 47);
+
+		// the swap exceeding the guard is retried in the next interval
+		CDPTreasuryModule::on_initialize(3);
+		assert_eq!(
+			CDPTreasuryModule::twap_sell_order(SERP),
+			Some(TwapSellOrder {
+				remaining: 20,
+				next_swap_at: 5,
+			})
+		);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 47);
+
+		// the slice is not swapped without oracle price
+		MockPriceSource::set_relative_price(None);
+		CDPTreasuryModule::on_initialize(5);
+		assert_eq!(
+			CDPTreasuryModule::twap_sell_order(SERP),
+			Some(TwapSellOrder {
+				remaining: 20,
+				next_swap_at: 7,
+			})
+		);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 47);
+
+		assert_noop!(CDPTreasuryModule::cancel_twap_sell(Origin::signed(5), SERP), BadOrigin);
+		assert_ok!(CDPTreasuryModule::cancel_twap_sell(Origin::signed(1), SERP));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::TwapSellCancelled {
+			collateral_type: SERP,
+			remaining_collateral_amount: 20,
+		}));
+		assert_eq!(CDPTreasuryModule::twap_sell_order(SERP), None);
+		assert_eq!(CDPTreasuryModule::idle_collaterals(SERP), 990);
+	});
+}
+
+#[test]
+fn set_lot_split_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = LotSplitParams {
+			strategy: LotSplitStrategy::Geometric {
+				ratio: Ratio::saturating_from_integer(2),
+			},
+			stagger_interval: 10,
+		};
+		assert_eq!(CDPTreasuryModule::lot_split_params(SERP), Default::default());
+		assert_noop!(
+			CDPTreasuryModule::set_lot_split_params(Origin::signed(5), SERP, params.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_lot_split_params(
+				Origin::signed(1),
+				SERP,
+				LotSplitParams {
+					strategy: LotSplitStrategy::Geometric { ratio: Ratio::zero() },
+					stagger_interval: 10,
+				}
+			),
+			Error::<Runtime>::InvalidLotSplitParams
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_lot_split_params(
+				Origin::signed(1),
+				SERP,
+				LotSplitParams {
+					strategy: LotSplitStrategy::ValueBased { lot_value: 0 },
+					stagger_interval: 10,
+				}
+			),
+			Error::<Runtime>::InvalidLotSplitParams
+		);
+		assert_ok!(CDPTreasuryModule::set_lot_split_params(
+			Origin::signed(1),
+			SERP,
+			params.clone()
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::LotSplitParamsUpdated {
+			collateral_type: SERP,
+			new_params: params.clone(),
+		}));
+		assert_eq!(CDPTreasuryModule::lot_split_params(SERP), params);
+	});
+}
+
+#[test]
+fn split_collateral_auction_lots_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			Origin::signed(1),
+			SERP,
+			300
+		));
+
+		// fixed size
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 1000, 999, false),
+			vec![(1000, 999)]
+		);
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 1000, 999, true),
+			vec![(250, 249), (250, 249), (250, 249), (250, 252)]
+		);
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 2001, 1000, true),
+			vec![(400, 199), (400, 199), (400, 199), (400, 199), (401, 204)]
+		);
+
+		// value based, falls back to fixed size without oracle price
+		assert_ok!(CDPTreasuryModule::set_lot_split_params(
+			Origin::signed(1),
+			SERP,
+			LotSplitParams {
+				strategy: LotSplitStrategy::ValueBased { lot_value: 1000 },
+				stagger_interval: 0,
+			}
+		));
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 1000, 1001, true),
+			vec![(250, 250), (250, 250), (250, 250), (250, 251)]
+		);
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(5)));
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 1000, 1001, true),
+			vec![(200, 200), (200, 200), (200, 200), (200, 200), (200, 201)]
+		);
+
+		// geometric
+		assert_ok!(CDPTreasuryModule::set_lot_split_params(
+			Origin::signed(1),
+			SERP,
+			LotSplitParams {
+				strategy: LotSplitStrategy::Geometric {
+					ratio: Ratio::saturating_from_integer(2),
+				},
+				stagger_interval: 0,
+			}
+		));
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 1000, 1000, true),
+			vec![(300, 300), (600, 600), (100, 100)]
+		);
+		assert_ok!(CDPTreasuryModule::set_lot_split_params(
+			Origin::signed(1),
+			SERP,
+			LotSplitParams {
+				strategy: LotSplitStrategy::Geometric {
+					ratio: Ratio::saturating_from_rational(1, 2),
+				},
+				stagger_interval: 0,
+			}
+		));
+		assert_eq!(
+			CDPTreasuryModule::split_collateral_auction_lots(SERP, 1000, 1000, true),
+			vec![(300, 300), (150, 150), (75, 75), (37, 37), (438, 438)]
+		);
+	});
+}
+
+#[test]
+fn split_collateral_auction_lots_sum_equal_to_inputs() {
+	ExtBuilder::default().build().execute_with(|| {
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(7, 3)));
+		let strategies = vec![
+			LotSplitStrategy::FixedSize,
+			LotSplitStrategy::ValueBased { lot_value: 777 },
+			LotSplitStrategy::Geometric {
+				ratio: Ratio::saturating_from_rational(3, 2),
+			},
+			LotSplitStrategy::Geometric {
+				ratio: Ratio::saturating_from_rational(1, 3),
+			},
+		];
+
+		for size in vec![0, 1, 299, 300] {
+			assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+				Origin::signed(1),
+				SERP,
+				size
+			));
+			for strategy in strategies.iter() {
+				assert_ok!(CDPTreasuryModule::set_lot_split_params(
+					Origin::signed(1),
+					SERP,
+					LotSplitParams {
+						strategy: *strategy,
+						stagger_interval: 0,
+					}
+				));
+				for amount in vec![0, 1, 7, 299, 300, 301, 999, 1000, 12345] {
+					for target in vec![0, 1, 3, 999, 1000, 77777] {
+						let lots = CDPTreasuryModule::split_collateral_auction_lots(SERP, amount, target, true);
+						assert!(!lots.is_empty() && lots.len() <= 5);
+						assert_eq!(lots.iter().map(|(a, _)| a).sum::<Balance>(), amount);
+						assert_eq!(lots.iter().map(|(_, t)| t).sum::<Balance>(), target);
+					}
+				}
+			}
+		}
+	});
+}
+
+#[test]
+fn create_staggered_collateral_auctions_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(SERP, &CDPTreasuryModule::account_id(), 10000));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			Origin::signed(1),
+			SERP,
+			300
+		));
+		assert_ok!(CDPTreasuryModule::set_lot_split_params(
+			Origin::signed(1),
+			SERP,
+			LotSplitParams {
+				strategy: LotSplitStrategy::FixedSize,
+				stagger_interval: 10,
+			}
+		));
+
+		// the first lot begins at once, the others begin every 10 blocks
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			SERP, 1000, 1000, ALICE, true
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::CollateralAuctionLotScheduled {
+			collateral_type: SERP,
+			amount: 250,
+			target: 250,
+			start_at: 31,
+		}));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 250);
+		assert_eq!(CDPTreasuryModule::total_collateral_in_pending_lots(SERP), 750);
+		assert_eq!(CDPTreasuryModule::total_target_in_pending_lots(), 750);
+		assert_eq!(CDPTreasuryModule::total_target_in_auction(), 1000);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(SERP), 9000);
+		assert_eq!(
+			CDPTreasuryModule::pending_collateral_lots(11),
+			vec![PendingCollateralLot {
+				refund_receiver: ALICE,
+				currency_id: SERP,
+				amount: 250,
+				target: 250,
+			}]
+		);
+
+		// collateral in pending lots can not be auctioned again
+		assert_noop!(
+			CDPTreasuryModule::create_collateral_auctions(SERP, 9001, 1000, ALICE, true),
+			Error::<Runtime>::CollateralNotEnough,
+		);
+
+		CDPTreasuryModule::on_initialize(11);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 2);
+		assert_eq!(CDPTreasuryModule::total_collateral_in_pending_lots(SERP), 500);
+		assert_eq!(CDPTreasuryModule::pending_collateral_lots(11), vec![]);
+
+		CDPTreasuryModule::on_initialize(21);
+		CDPTreasuryModule::on_initialize(31);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 4);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 1000);
+		assert_eq!(TOTAL_TARGET_IN_AUCTION.with(|v| *v.borrow_mut()), 1000);
+		assert_eq!(CDPTreasuryModule::total_collateral_in_pending_lots(SERP), 0);
+		assert_eq!(CDPTreasuryModule::total_target_in_pending_lots(), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals_not_in_auction(SERP), 9000);
+	});
+}
+
+#[test]
+fn staggered_collateral_auctions_begin_at_once_after_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(SERP, &CDPTreasuryModule::account_id(), 10000));
+		assert_ok!(CDPTreasuryModule::set_expected_collateral_auction_size(
+			Origin::signed(1),
+			SERP,
+			300
+		));
+		assert_ok!(CDPTreasuryModule::set_lot_split_params(
+			Origin::signed(1),
+			SERP,
+			LotSplitParams {
+				strategy: LotSplitStrategy::FixedSize,
+				stagger_interval: 10,
+			}
+		));
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			SERP, 1000, 1000, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(CDPTreasuryModule::total_collateral_in_pending_lots(SERP), 750);
+
+		// the lots waiting to start are flushed after shutdown
+		mock_shutdown();
+		CDPTreasuryModule::on_initialize(2);
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 4);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 1000);
+		assert_eq!(CDPTreasuryModule::total_collateral_in_pending_lots(SERP), 0);
+		assert_eq!(CDPTreasuryModule::total_target_in_pending_lots(), 0);
+		assert_eq!(CDPTreasuryModule::pending_collateral_lots(31), vec![]);
+
+		// no more lots are staggered after shutdown
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			SERP, 1000, 1000, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 8);
+		assert_eq!(CDPTreasuryModule::total_collateral_in_pending_lots(SERP), 0);
+	});
+}
+
+#[test]
+fn balance_sheet_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(SERP, &CDPTreasuryModule::account_id(), 1000));
+		assert_ok!(Currencies::deposit(DNAR, &CDPTreasuryModule::account_id(), 500));
+		assert_ok!(Currencies::deposit(SETUSD, &CDPTreasuryModule::account_id(), 300));
+		assert_ok!(CDPTreasuryModule::on_system_debit(100));
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			SERP, 200, 400, ALICE, false
+		));
+
+		// collaterals are not valued without oracle price
+		assert_eq!(
+			CDPTreasuryModule::balance_sheet(),
+			BalanceSheet {
+				block_number: 1,
+				surplus_pool: 300,
+				debit_pool: 100,
+				stable_currencies: vec![
+					StableCurrencyHolding {
+						currency_id: SETUSD,
+						surplus_pool: 300,
+						debit_pool: 100,
+					},
+					StableCurrencyHolding {
+						currency_id: SETR,
+						surplus_pool: 0,
+						debit_pool: 0,
+					},
+				],
+				insurance_fund: 0,
+				total_target_in_auction: 400,
+				total_debit_in_auction: 0,
+				collaterals: vec![
+					CollateralHolding {
+						currency_id: SERP,
+						total: 1000,
+						in_auction: 200,
+						in_pending_lots: 0,
+						in_twap_sells: 0,
+						value: None,
+					},
+					CollateralHolding {
+						currency_id: DNAR,
+						total: 500,
+						in_auction: 200,
+						in_pending_lots: 0,
+						in_twap_sells: 0,
+						value: None,
+					},
+				],
+				total_collateral_value: 0,
+			}
+		);
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(5)));
+		let balance_sheet = CDPTreasuryModule::balance_sheet();
+		assert_eq!(balance_sheet.collaterals[0].value, Some(5000));
+		assert_eq!(balance_sheet.collaterals[1].value, Some(2500));
+		assert_eq!(balance_sheet.total_collateral_value, 7500);
+	});
+}
+
+#[test]
+fn balance_sheet_snapshot_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_balance_sheet_snapshot_params(Origin::signed(5), 10, 2),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_balance_sheet_snapshot_params(Origin::signed(1), 10, 4),
+			Error::<Runtime>::InvalidMaxBalanceSheetSnapshots
+		);
+		assert_ok!(CDPTreasuryModule::set_balance_sheet_snapshot_params(
+			Origin::signed(1),
+			10,
+			2
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::BalanceSheetSnapshotParamsUpdated {
+			new_period: 10,
+			new_max_snapshots: 2,
+		}));
+		assert_eq!(CDPTreasuryModule::balance_sheet_snapshot_period(), 10);
+		assert_eq!(CDPTreasuryModule::max_balance_sheet_snapshots(), 2);
+
+		CDPTreasuryModule::on_initialize(5);
+		assert_eq!(CDPTreasuryModule::balance_sheet_snapshots(), vec![]);
+
+		assert_ok!(Currencies::deposit(SETUSD, &CDPTreasuryModule::account_id(), 300));
+		CDPTreasuryModule::on_initialize(10);
+		assert_ok!(Currencies::deposit(SETUSD, &CDPTreasuryModule::account_id(), 100));
+		CDPTreasuryModule::on_initialize(20);
+		CDPTreasuryModule::on_initialize(30);
+
+		// the oldest snapshot is dropped
+		let snapshots = CDPTreasuryModule::balance_sheet_snapshots();
+		assert_eq!(
+			snapshots
+				.iter()
+				.map(|snapshot| (snapshot.block_number, snapshot.surplus_pool))
+				.collect::<Vec<_>>(),
+			vec![(20, 400), (30, 400)]
+		);
+		assert_eq!(CDPTreasuryModule::balance_sheet_snapshot_count(), 3);
+
+		// the ring keeps at most `BalanceSheetSnapshotCapacity` snapshots
+		assert_ok!(CDPTreasuryModule::set_balance_sheet_snapshot_params(
+			Origin::signed(1),
+			10,
+			3
+		));
+		CDPTreasuryModule::on_initialize(40);
+		assert_eq!(
+			CDPTreasuryModule::balance_sheet_snapshots()
+				.iter()
+				.map(|snapshot| snapshot.block_number)
+				.collect::<Vec<_>>(),
+			vec![20, 30, 40]
+		);
+		assert_eq!(BalanceSheetSnapshots::<Runtime>::iter().count(), 3);
+	});
+}
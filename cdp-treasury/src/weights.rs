@@ -41,6 +41,21 @@ pub trait WeightInfo {
 	fn auction_collateral(b: u32) -> Weight;
 	fn exchange_collateral_to_stable() -> Weight;
 	fn set_expected_collateral_auction_size() -> Weight;
+	fn set_surplus_buffer() -> Weight;
+	fn set_surplus_extraction_params() -> Weight;
+	fn set_debit_auction_params() -> Weight;
+	fn set_collateral_sell_policy() -> Weight;
+	fn set_collateral_sell_period() -> Weight;
+	fn set_insurance_fund_params() -> Weight;
+	fn release_insurance_fund() -> Weight;
+	fn set_swap_path_joint_list() -> Weight;
+	fn set_swap_price_guard() -> Weight;
+	fn cancel_twap_sell() -> Weight;
+	fn set_lot_split_params() -> Weight;
+	fn set_balance_sheet_snapshot_params() -> Weight;
+	fn pause_operation() -> Weight;
+	fn unpause_operation() -> Weight;
+	fn on_initialize(c: u32) -> Weight;
 }
 
 /// Weights for cdp_treasury using the Setheum node and recommended hardware.
@@ -68,6 +83,73 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 		(25_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_buffer() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_surplus_extraction_params() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_debit_auction_params() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_collateral_sell_policy() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_collateral_sell_period() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_insurance_fund_params() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn release_insurance_fund() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_swap_path_joint_list() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_swap_price_guard() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_twap_sell() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_lot_split_params() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_balance_sheet_snapshot_params() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn pause_operation() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn unpause_operation() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(128_000_000 as Weight)
+			.saturating_add((176_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -93,4 +175,71 @@ impl WeightInfo for () {
 		(25_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_buffer() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_surplus_extraction_params() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_debit_auction_params() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_collateral_sell_policy() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_collateral_sell_period() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_insurance_fund_params() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn release_insurance_fund() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_swap_path_joint_list() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_swap_price_guard() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_twap_sell() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_lot_split_params() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_balance_sheet_snapshot_params() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn pause_operation() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn unpause_operation() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(128_000_000 as Weight)
+			.saturating_add((176_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((9 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
@@ -152,8 +152,8 @@ pub mod module {
 
 			// Ensure all debits of CDPs have been settled, and all collateral auction has
 			// been done or canceled. Settle all collaterals type CDPs which have debit,
-			// cancel all collateral auctions in forward stage and wait for all collateral
-			// auctions in reverse stage to be ended.
+			// cancel all collateral auctions in forward stage and settle all collateral
+			// auctions in reverse stage to the last bidders.
 			let collateral_currency_ids = T::CollateralCurrencyIds::get();
 			for currency_id in collateral_currency_ids {
 				// there's no collateral auction
@@ -20,7 +20,7 @@ use sp_runtime::{
 	traits::IdentityLookup,
 	DispatchResult,
 };
-use support::{AuctionManager, LockablePrice, RiskManager, SerpTreasury};
+use support::{AuctionManager, LockablePrice, Price, PriceProvider, RiskManager, SerpTreasury};
 
 pub type AccountId = u128;
 pub type AuctionId = u32;
@@ -142,7 +142,10 @@ impl loans::Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
 	type RiskManager = MockRiskManager;
+	type MintFeeManager = ();
+	type DebitCurrencyProvider = loans::DefaultDebitCurrency<GetSetUSDId>;
 	type CDPTreasury = CDPTreasuryModule;
+	type OnUpdateLoan = ();
 	type PalletId = LoansPalletId;
 }
 
@@ -157,6 +160,17 @@ impl LockablePrice<CurrencyId> for MockLockablePrice {
 	}
 }
 
+pub struct MockPriceSource;
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type Balance = Balance;
@@ -344,7 +358,9 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const GetSetUSDId: CurrencyId = SETUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![SETUSD];
 	pub const MaxAuctionsCount: u32 = 10_000;
+	pub const BalanceSheetSnapshotCapacity: u32 = 10;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![];
 }
@@ -353,13 +369,19 @@ impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type GetSetUSDId = GetSetUSDId;
+	type StableCurrencyIds = StableCurrencyIds;
 	type AuctionManagerHandler = MockAuctionManager;
+	type DebitAuctionManagerHandler = ();
+	type EmergencyShutdown = EmergencyShutdownModule;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
+	type PriceSource = MockPriceSource;
+	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type SerpTreasury = MockSerpTreasury;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type BalanceSheetSnapshotCapacity = BalanceSheetSnapshotCapacity;
 	type WeightInfo = ();
 }
 
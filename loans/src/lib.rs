@@ -18,14 +18,15 @@
 
 use codec::MaxEncodedLen;
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended};
 use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
 	traits::{AccountIdConversion, Zero},
-	ArithmeticError, DispatchResult, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
-use sp_std::convert::TryInto;
-use support::{CDPTreasury, RiskManager};
+use sp_std::{convert::TryInto, marker::PhantomData};
+use cdp_treasury::{BadDebtManager, MultiStableCurrencyTreasury};
+use support::{CDPTreasury, Rate, RiskManager};
 
 mod mock;
 mod tests;
@@ -41,6 +42,85 @@ pub struct Position {
 	pub debit: Balance,
 }
 
+/// Abstraction over the one-off fee charged on newly issued debit.
+pub trait MintFeeManager {
+	/// Get the fee rate charged on the debit value newly issued under
+	/// `currency_id`.
+	fn get_mint_fee_rate(currency_id: CurrencyId) -> Rate;
+}
+
+impl MintFeeManager for () {
+	fn get_mint_fee_rate(_currency_id: CurrencyId) -> Rate {
+		Rate::zero()
+	}
+}
+
+/// Abstraction over the stable currency in which the debit of a collateral
+/// type is issued.
+pub trait DebitCurrencyProvider {
+	/// Get the stable currency in which the debit under `currency_id` is
+	/// issued.
+	fn get_debit_currency_id(currency_id: CurrencyId) -> CurrencyId;
+}
+
+/// Issue the debit of all collateral types in the stable currency `G`.
+pub struct DefaultDebitCurrency<G>(PhantomData<G>);
+
+impl<G: Get<CurrencyId>> DebitCurrencyProvider for DefaultDebitCurrency<G> {
+	fn get_debit_currency_id(_currency_id: CurrencyId) -> CurrencyId {
+		G::get()
+	}
+}
+
+/// Abstraction over the positions funding collateral auction bids.
+pub trait AuctionBidFunding<AccountId> {
+	/// Increase the debit of the position of `who` under `currency_id` by
+	/// `debit_amount` without risk check, and issue the debit value in
+	/// `stable_currency_id` to `to`. The whole debit value is issued so it
+	/// can always repay the debit, the mint fee is only charged by
+	/// `deposit_won_collateral` if the bid wins. Return the issued amount and
+	/// the mint fee on it.
+	fn borrow_for_bid(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		debit_amount: Balance,
+		stable_currency_id: CurrencyId,
+		to: &AccountId,
+	) -> Result<(Balance, Balance), DispatchError>;
+
+	/// Burn `debit_value` issued by `borrow_for_bid` from `from` and decrease
+	/// the debit of the position of `who` under `currency_id` by
+	/// `debit_amount`.
+	fn repay_bid(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		debit_amount: Balance,
+		debit_value: Balance,
+		from: &AccountId,
+	) -> DispatchResult;
+
+	/// Move `amount` collateral won by `who` from cdp treasury into its
+	/// position under `currency_id`, and charge the `mint_fee` held by `from`
+	/// on the debit funding the winning bid.
+	fn deposit_won_collateral(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		mint_fee: Balance,
+		from: &AccountId,
+	) -> DispatchResult;
+
+	/// Check the position of `who` under `currency_id` is valid with the
+	/// increments of collateral and debit.
+	fn check_position_valid(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		collateral_increase: Balance,
+		debit_increase: Balance,
+		check_required_ratio: bool,
+	) -> DispatchResult;
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -61,9 +141,23 @@ pub mod module {
 		/// Risk manager is used to limit the debit size of CDP
 		type RiskManager: RiskManager<Self::AccountId, CurrencyId, Balance, Balance>;
 
+		/// Mint fee manager is used to get the one-off fee rate charged when
+		/// issuing debit
+		type MintFeeManager: MintFeeManager;
+
+		/// Debit currency provider is used to get the stable currency in which
+		/// the debit of a collateral type is issued
+		type DebitCurrencyProvider: DebitCurrencyProvider;
+
 		/// CDP treasury for issuing/burning stable currency adjust debit value
 		/// adjustment
-		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>
+			+ BadDebtManager
+			+ MultiStableCurrencyTreasury<Self::AccountId>;
+
+		/// Handler called with the collateral type and its total debit before
+		/// and after an update of debit
+		type OnUpdateLoan: Happened<(CurrencyId, Balance, Balance)>;
 
 		/// The loan's module id, keep all collaterals of CDPs.
 		#[pallet::constant]
@@ -73,6 +167,8 @@ pub mod module {
 	#[pallet::error]
 	pub enum Error<T> {
 		AmountConvertFailed,
+		/// The debit of the collateral is not issued in the stable currency
+		InvalidDebitCurrency,
 	}
 
 	#[pallet::event]
@@ -99,6 +195,12 @@ pub mod module {
 			to: T::AccountId,
 			currency_id: CurrencyId,
 		},
+		/// Charge the one-off mint fee on newly issued debit.
+		MintFeeCharged {
+			owner: T::AccountId,
+			collateral_type: CurrencyId,
+			fee_amount: Balance,
+		},
 	}
 
 	/// The collateralized debit positions, map from
@@ -150,9 +252,13 @@ impl<T: Config> Pallet<T> {
 		// transfer collateral to cdp treasury
 		T::CDPTreasury::deposit_collateral(&Self::account_id(), currency_id, collateral_confiscate)?;
 
-		// deposit debit to cdp treasury
+		// deposit debit to cdp treasury and record it under the collateral type
 		let bad_debt_value = T::RiskManager::get_debit_value(currency_id, debit_decrease);
-		T::CDPTreasury::on_system_debit(bad_debt_value)?;
+		T::CDPTreasury::on_collateral_debit(
+			currency_id,
+			T::DebitCurrencyProvider::get_debit_currency_id(currency_id),
+			bad_debt_value,
+		)?;
 
 		// update loan
 		Self::update_loan(
@@ -195,20 +301,21 @@ impl<T: Config> Pallet<T> {
 			T::Currency::transfer(currency_id, &module_account, who, collateral_balance_adjustment)?;
 		}
 
+		let stable_currency_id = T::DebitCurrencyProvider::get_debit_currency_id(currency_id);
 		if debit_adjustment.is_positive() {
 			// check debit cap when increase debit
 			T::RiskManager::check_debit_cap(currency_id, Self::total_positions(currency_id).debit)?;
 
-			// issue debit with collateral backed by cdp treasury
-			T::CDPTreasury::issue_debit(
-				who,
-				T::RiskManager::get_debit_value(currency_id, debit_balance_adjustment),
-				true,
-			)?;
+			// issue debit with collateral backed by cdp treasury, the mint fee is
+			// deducted from the issued stable coin
+			let debit_value = T::RiskManager::get_debit_value(currency_id, debit_balance_adjustment);
+			let mint_fee = Self::charge_mint_fee(who, currency_id, debit_value)?;
+			T::CDPTreasury::issue_debit_of(stable_currency_id, who, debit_value.saturating_sub(mint_fee), true)?;
 		} else if debit_adjustment.is_negative() {
 			// repay debit
 			// burn debit by cdp treasury
-			T::CDPTreasury::burn_debit(
+			T::CDPTreasury::burn_debit_of(
+				stable_currency_id,
 				who,
 				T::RiskManager::get_debit_value(currency_id, debit_balance_adjustment),
 			)?;
@@ -226,6 +333,44 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// charge the one-off mint fee on `debit_value` newly issued under
+	/// `currency_id`, the fee is deposited to cdp treasury as system surplus.
+	/// Return the actual fee amount.
+	pub fn charge_mint_fee(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		debit_value: Balance,
+	) -> Result<Balance, DispatchError> {
+		let fee_amount = Self::mint_fee_of(currency_id, debit_value);
+		Self::deposit_mint_fee(who, currency_id, fee_amount)?;
+		Ok(fee_amount)
+	}
+
+	/// Return the one-off mint fee on `debit_value` newly issued under
+	/// `currency_id`.
+	fn mint_fee_of(currency_id: CurrencyId, debit_value: Balance) -> Balance {
+		T::MintFeeManager::get_mint_fee_rate(currency_id)
+			.saturating_mul_int(debit_value)
+			.min(debit_value)
+	}
+
+	/// Deposit the mint fee charged to `who` to cdp treasury as system
+	/// surplus.
+	fn deposit_mint_fee(who: &T::AccountId, currency_id: CurrencyId, fee_amount: Balance) -> DispatchResult {
+		if !fee_amount.is_zero() {
+			T::CDPTreasury::on_system_surplus_of(
+				T::DebitCurrencyProvider::get_debit_currency_id(currency_id),
+				fee_amount,
+			)?;
+			Self::deposit_event(Event::MintFeeCharged {
+				owner: who.clone(),
+				collateral_type: currency_id,
+				fee_amount,
+			});
+		}
+		Ok(())
+	}
+
 	/// transfer whole loan of `from` to `to`
 	pub fn transfer_loan(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		// get `from` position data
@@ -321,6 +466,7 @@ impl<T: Config> Pallet<T> {
 			Ok(())
 		})?;
 
+		let previous_total_debit = Self::total_positions(currency_id).debit;
 		TotalPositions::<T>::try_mutate(currency_id, |total_positions| -> DispatchResult {
 			total_positions.collateral = if collateral_adjustment.is_positive() {
 				total_positions
@@ -349,6 +495,14 @@ impl<T: Config> Pallet<T> {
 			Ok(())
 		})?;
 
+		if !debit_adjustment.is_zero() {
+			T::OnUpdateLoan::happened(&(
+				currency_id,
+				previous_total_debit,
+				Self::total_positions(currency_id).debit,
+			));
+		}
+
 		Self::deposit_event(Event::PositionUpdated {
 			owner: who.clone(),
 			collateral_type: currency_id,
@@ -359,6 +513,89 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> AuctionBidFunding<T::AccountId> for Pallet<T> {
+	#[transactional]
+	fn borrow_for_bid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		debit_amount: Balance,
+		stable_currency_id: CurrencyId,
+		to: &T::AccountId,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(
+			T::DebitCurrencyProvider::get_debit_currency_id(currency_id) == stable_currency_id,
+			Error::<T>::InvalidDebitCurrency
+		);
+		let debit_adjustment = Self::amount_try_from_balance(debit_amount)?;
+		Self::update_loan(who, currency_id, Zero::zero(), debit_adjustment)?;
+
+		// check debit cap when increase debit
+		T::RiskManager::check_debit_cap(currency_id, Self::total_positions(currency_id).debit)?;
+
+		// issue the whole debit value, the mint fee is not charged as the debit is
+		// repaid if the bid is outbid
+		let debit_value = T::RiskManager::get_debit_value(currency_id, debit_amount);
+		T::CDPTreasury::issue_debit_of(stable_currency_id, to, debit_value, true)?;
+
+		Ok((debit_value, Self::mint_fee_of(currency_id, debit_value)))
+	}
+
+	#[transactional]
+	fn repay_bid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		debit_amount: Balance,
+		debit_value: Balance,
+		from: &T::AccountId,
+	) -> DispatchResult {
+		let debit_adjustment = Self::amount_try_from_balance(debit_amount)?;
+		Self::update_loan(who, currency_id, Zero::zero(), debit_adjustment.saturating_neg())?;
+		T::CDPTreasury::burn_debit_of(
+			T::DebitCurrencyProvider::get_debit_currency_id(currency_id),
+			from,
+			debit_value,
+		)
+	}
+
+	#[transactional]
+	fn deposit_won_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		mint_fee: Balance,
+		from: &T::AccountId,
+	) -> DispatchResult {
+		let collateral_adjustment = Self::amount_try_from_balance(amount)?;
+		T::CDPTreasury::withdraw_collateral(&Self::account_id(), currency_id, amount)?;
+		Self::update_loan(who, currency_id, collateral_adjustment, Zero::zero())?;
+
+		// the fee held by `from` is burned and deposited to cdp treasury as system
+		// surplus, the same as the fee charged on issuing debit
+		T::CDPTreasury::burn_debit_of(
+			T::DebitCurrencyProvider::get_debit_currency_id(currency_id),
+			from,
+			mint_fee,
+		)?;
+		Self::deposit_mint_fee(who, currency_id, mint_fee)
+	}
+
+	fn check_position_valid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_increase: Balance,
+		debit_increase: Balance,
+		check_required_ratio: bool,
+	) -> DispatchResult {
+		let Position { collateral, debit } = Self::positions(currency_id, who);
+		T::RiskManager::check_position_valid(
+			currency_id,
+			collateral.checked_add(collateral_increase).ok_or(ArithmeticError::Overflow)?,
+			debit.checked_add(debit_increase).ok_or(ArithmeticError::Overflow)?,
+			check_required_ratio,
+		)
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	/// Convert `Balance` to `Amount`.
 	pub fn amount_try_from_balance(b: Balance) -> Result<Amount, Error<T>> {
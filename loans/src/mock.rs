@@ -21,7 +21,7 @@ use sp_runtime::{
 };
 use sp_std::cell::RefCell;
 use std::collections::HashMap;
-use support::{AuctionManager, RiskManager, SerpTreasury};
+use support::{AuctionManager, EmergencyShutdown, Price, PriceProvider, RiskManager, SerpTreasury};
 
 pub type AccountId = u128;
 pub type AuctionId = u32;
@@ -115,6 +115,17 @@ impl orml_currencies::Config for Runtime {
 }
 pub type AdaptedBasicCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, PalletBalances, Amount, BlockNumber>;
 
+pub struct MockPriceSource;
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type CurrencyId = CurrencyId;
@@ -302,22 +313,38 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const GetSetUSDId: CurrencyId = SETUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![SETUSD];
 	pub const MaxAuctionsCount: u32 = 10_000;
+	pub const BalanceSheetSnapshotCapacity: u32 = 10;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");
+	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![SERP, DNAR];
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![];
 }
 
+pub struct MockEmergencyShutdown;
+impl EmergencyShutdown for MockEmergencyShutdown {
+	fn is_shutdown() -> bool {
+		false
+	}
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type GetSetUSDId = GetSetUSDId;
+	type StableCurrencyIds = StableCurrencyIds;
 	type AuctionManagerHandler = MockAuctionManager;
+	type DebitAuctionManagerHandler = ();
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
+	type PriceSource = MockPriceSource;
+	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type SerpTreasury = MockSerpTreasury;
 	type PalletId = CDPTreasuryPalletId;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type BalanceSheetSnapshotCapacity = BalanceSheetSnapshotCapacity;
 	type WeightInfo = ();
 }
 
@@ -362,6 +389,22 @@ thread_local! {
 	pub static DNAR_SHARES: RefCell<HashMap<AccountId, Balance>> = RefCell::new(HashMap::new());
 }
 
+thread_local! {
+	static MINT_FEE_RATE: RefCell<Rate> = RefCell::new(Rate::zero());
+}
+
+pub struct MockMintFeeManager;
+impl MockMintFeeManager {
+	pub fn set_mint_fee_rate(rate: Rate) {
+		MINT_FEE_RATE.with(|v| *v.borrow_mut() = rate);
+	}
+}
+impl MintFeeManager for MockMintFeeManager {
+	fn get_mint_fee_rate(_currency_id: CurrencyId) -> Rate {
+		MINT_FEE_RATE.with(|v| *v.borrow())
+	}
+}
+
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"set/loan");
 }
@@ -370,7 +413,10 @@ impl Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type RiskManager = MockRiskManager;
+	type MintFeeManager = MockMintFeeManager;
+	type DebitCurrencyProvider = DefaultDebitCurrency<GetSetUSDId>;
 	type CDPTreasury = CDPTreasuryModule;
+	type OnUpdateLoan = ();
 	type PalletId = LoansPalletId;
 }
 
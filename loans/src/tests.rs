@@ -194,6 +194,7 @@ fn confiscate_collateral_and_debit_work() {
 		assert_ok!(LoansModule::confiscate_collateral_and_debit(&ALICE, SERP, 300, 200));
 		assert_eq!(CDPTreasuryModule::get_total_collaterals(SERP), 300);
 		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+		assert_eq!(CDPTreasuryModule::bad_debt_report(SERP).bad_debt, 100);
 		assert_eq!(LoansModule::positions(SERP, &ALICE).debit, 100);
 		assert_eq!(LoansModule::positions(SERP, &ALICE).collateral, 200);
 		System::assert_last_event(Event::LoansModule(crate::Event::ConfiscateCollateralAndDebit {
@@ -205,6 +206,81 @@ fn confiscate_collateral_and_debit_work() {
 	});
 }
 
+#[test]
+fn auction_bid_funding_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(LoansModule::adjust_position(&ALICE, SERP, 500, 100));
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 50);
+
+		// mock exceed debit value cap
+		assert_noop!(
+			LoansModule::borrow_for_bid(&ALICE, SERP, 900, SETUSD, &BOB),
+			sp_runtime::DispatchError::Other("mock exceed debit value cap error")
+		);
+		assert_noop!(
+			LoansModule::borrow_for_bid(&ALICE, SERP, 200, DNAR, &BOB),
+			Error::<Runtime>::InvalidDebitCurrency
+		);
+
+		// the debit value is issued to the recipient without mint fee
+		let bob_balance = Currencies::free_balance(SETUSD, &BOB);
+		assert_eq!(LoansModule::borrow_for_bid(&ALICE, SERP, 200, SETUSD, &BOB), Ok((100, 0)));
+		assert_eq!(LoansModule::positions(SERP, &ALICE).debit, 300);
+		assert_eq!(LoansModule::total_positions(SERP).debit, 300);
+		assert_eq!(Currencies::free_balance(SETUSD, &ALICE), 50);
+		assert_eq!(Currencies::free_balance(SETUSD, &BOB), bob_balance + 100);
+
+		assert_ok!(LoansModule::repay_bid(&ALICE, SERP, 200, 100, &BOB));
+		assert_eq!(LoansModule::positions(SERP, &ALICE).debit, 100);
+		assert_eq!(LoansModule::total_positions(SERP).debit, 100);
+		assert_eq!(Currencies::free_balance(SETUSD, &BOB), bob_balance);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, SERP, 100));
+		assert_ok!(LoansModule::deposit_won_collateral(&ALICE, SERP, 100, 0, &BOB));
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(SERP), 0);
+		assert_eq!(LoansModule::positions(SERP, &ALICE).collateral, 600);
+		assert_eq!(Currencies::free_balance(SERP, &LoansModule::account_id()), 600);
+
+		assert_ok!(<LoansModule as AuctionBidFunding<_>>::check_position_valid(
+			&ALICE, SERP, 100, 100, true
+		));
+		assert_noop!(
+			<LoansModule as AuctionBidFunding<_>>::check_position_valid(&ALICE, DNAR, 100, 100, false),
+			sp_runtime::DispatchError::Other("mock below liquidation ratio error")
+		);
+	});
+}
+
+#[test]
+fn won_loan_funded_bid_charge_mint_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		MockMintFeeManager::set_mint_fee_rate(Rate::saturating_from_rational(1, 10));
+		assert_ok!(LoansModule::adjust_position(&ALICE, SERP, 500, 0));
+
+		// the whole debit value is issued, and the fee is not charged yet
+		let bob_balance = Currencies::free_balance(SETUSD, &BOB);
+		let surplus_pool = CDPTreasuryModule::surplus_pool();
+		assert_eq!(LoansModule::borrow_for_bid(&ALICE, SERP, 200, SETUSD, &BOB), Ok((100, 10)));
+		assert_eq!(Currencies::free_balance(SETUSD, &BOB), bob_balance + 100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), surplus_pool);
+
+		// the fee held by the escrow is charged when the bid wins
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, SERP, 100));
+		assert_ok!(LoansModule::deposit_won_collateral(&ALICE, SERP, 100, 10, &BOB));
+		System::assert_last_event(Event::LoansModule(crate::Event::MintFeeCharged {
+			owner: ALICE,
+			collateral_type: SERP,
+			fee_amount: 10,
+		}));
+		assert_eq!(Currencies::free_balance(SETUSD, &BOB), bob_balance + 90);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), surplus_pool + 10);
+		assert_eq!(LoansModule::positions(SERP, &ALICE).collateral, 600);
+		assert_eq!(LoansModule::positions(SERP, &ALICE).debit, 200);
+	});
+}
+
 // #[test]
 // fn loan_updated_updated_when_adjust_collateral() {
 // 	ExtBuilder::default().build().execute_with(|| {
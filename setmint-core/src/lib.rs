@@ -18,6 +18,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
+use cdp_treasury::{OperationPauseManager, PausableOperation};
 use frame_support::{pallet_prelude::*, traits::NamedReservableCurrency, transactional};
 use frame_system::pallet_prelude::*;
 use primitives::{Amount, Balance, CurrencyId, ReserveIdentifier};
@@ -137,6 +138,10 @@ pub mod module {
 			if !debit_adjustment.is_zero() {
 				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
 			}
+			// repayments and deposits are kept live when adjusting loan is paused
+			if debit_adjustment.is_positive() || collateral_adjustment.is_negative() {
+				Self::ensure_operation_not_paused(PausableOperation::AdjustLoan, currency_id)?;
+			}
 			<cdp_engine::Pallet<T>>::adjust_position(&who, currency_id, collateral_adjustment, debit_adjustment)?;
 			Ok(())
 		}
@@ -156,6 +161,7 @@ pub mod module {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			Self::ensure_operation_not_paused(PausableOperation::CloseLoanByDex, currency_id)?;
 			<cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(who, currency_id, max_collateral_amount)?;
 			Ok(())
 		}
@@ -176,6 +182,7 @@ pub mod module {
 			let to = ensure_signed(origin)?;
 			let from = T::Lookup::lookup(from)?;
 			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			Self::ensure_operation_not_paused(PausableOperation::TransferLoan, currency_id)?;
 			Self::check_authorization(&from, &to, currency_id)?;
 			<loans::Pallet<T>>::transfer_loan(&from, &to, currency_id)?;
 			Ok(())
@@ -265,6 +272,7 @@ pub mod module {
 			min_increase_collateral: Balance,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::ensure_operation_not_paused(PausableOperation::ExpandPositionCollateral, currency_id)?;
 			<cdp_engine::Pallet<T>>::expand_position_collateral(
 				&who,
 				currency_id,
@@ -288,6 +296,7 @@ pub mod module {
 			min_decrease_debit_value: Balance,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::ensure_operation_not_paused(PausableOperation::ShrinkPositionDebit, currency_id)?;
 			<cdp_engine::Pallet<T>>::shrink_position_debit(
 				&who,
 				currency_id,
@@ -300,6 +309,11 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Ensure `operation` is not paused for `currency_id` by CDP treasury
+	fn ensure_operation_not_paused(operation: PausableOperation, currency_id: CurrencyId) -> DispatchResult {
+		<T as cdp_engine::Config>::CDPTreasury::ensure_operation_not_paused(operation, currency_id)
+	}
+
 	/// Check if `from` has the authorization of `to` under `currency_id`
 	fn check_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		ensure!(
@@ -125,7 +125,10 @@ impl loans::Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
 	type RiskManager = CDPEngineModule;
+	type MintFeeManager = CDPEngineModule;
+	type DebitCurrencyProvider = CDPEngineModule;
 	type CDPTreasury = CDPTreasuryModule;
+	type OnUpdateLoan = ();
 	type PalletId = LoansPalletId;
 }
 
@@ -342,7 +345,9 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const GetSetUSDId: CurrencyId = SETUSD;
+	pub StableCurrencyIds: Vec<CurrencyId> = vec![SETUSD];
 	pub const MaxAuctionsCount: u32 = 10_000;
+	pub const BalanceSheetSnapshotCapacity: u32 = 10;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"set/hztr").into_account();
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![
@@ -354,13 +359,19 @@ impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type GetSetUSDId = GetSetUSDId;
+	type StableCurrencyIds = StableCurrencyIds;
 	type AuctionManagerHandler = MockAuctionManager;
+	type DebitAuctionManagerHandler = ();
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
+	type PriceSource = MockPriceSource;
+	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type SerpTreasury = MockSerpTreasury;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type BalanceSheetSnapshotCapacity = BalanceSheetSnapshotCapacity;
 	type WeightInfo = ();
 }
 
@@ -382,6 +393,7 @@ parameter_types! {
 	pub const MinimumDebitValue: Balance = 2;
 	pub MaxSwapSlippageCompareToOracle: Ratio = Ratio::saturating_from_rational(50, 100);
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const MaxBatchSize: u32 = 50;
 }
 
 impl cdp_engine::Config for Runtime {
@@ -397,6 +409,7 @@ impl cdp_engine::Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageCompareToOracle;
 	type UnsignedPriority = UnsignedPriority;
+	type MaxBatchSize = MaxBatchSize;
 	type EmergencyShutdown = MockEmergencyShutdown;
 	type Currency = Currencies;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
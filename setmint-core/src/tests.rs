@@ -132,6 +132,51 @@ fn adjust_loan_should_work() {
 	});
 }
 
+#[test]
+fn paused_operations_should_not_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			SERP,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(SerpSetmint::adjust_loan(Origin::signed(ALICE), SERP, 100, 50));
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::AdjustLoan,
+			Some(SERP),
+			None
+		));
+		assert_ok!(CDPTreasuryModule::pause_operation(
+			Origin::signed(1),
+			PausableOperation::CloseLoanByDex,
+			None,
+			None
+		));
+
+		assert_noop!(
+			SerpSetmint::adjust_loan(Origin::signed(ALICE), SERP, 0, 10),
+			cdp_treasury::Error::<Runtime>::OperationPaused,
+		);
+		assert_noop!(
+			SerpSetmint::adjust_loan(Origin::signed(ALICE), SERP, -10, 0),
+			cdp_treasury::Error::<Runtime>::OperationPaused,
+		);
+		assert_noop!(
+			SerpSetmint::close_loan_has_debit_by_dex(Origin::signed(ALICE), SERP, 100),
+			cdp_treasury::Error::<Runtime>::OperationPaused,
+		);
+
+		// repayments and deposits are kept live
+		assert_ok!(SerpSetmint::adjust_loan(Origin::signed(ALICE), SERP, 10, -10));
+		assert_eq!(LoansModule::positions(SERP, ALICE).collateral, 110);
+		assert_eq!(LoansModule::positions(SERP, ALICE).debit, 40);
+	});
+}
+
 #[test]
 fn on_emergency_shutdown_should_work() {
 	ExtBuilder::default().build().execute_with(|| {